@@ -0,0 +1,116 @@
+// Enhancement rounds on the real input with the image as rows of strings the
+// way the day was first solved, as a grid of pixels and bit-packed, then many
+// more rounds on the packed image alone
+
+use std::fs;
+use std::path::Path;
+
+use aoc2021::bench::{self, Stats};
+use aoc2021::day20::{self, InfiniteImage, PackedImage, PairRule};
+
+const ITERATIONS: usize = 5;
+
+// The first solution, kept to measure against: the image grows by a border
+// of the background before each step and every output pixel scans its
+// neighborhood byte by byte
+mod strings {
+    fn expand(inputs: &mut Vec<String>, pixel: char) {
+        let border: String = [pixel; 2].iter().collect();
+
+        for input in inputs.iter_mut() {
+            *input = format!("{}{}{}", border, input, border);
+        }
+
+        let row: String = std::iter::repeat_n(pixel, inputs[0].len()).collect();
+
+        inputs.splice(0..0, [row.clone(), row.clone()]);
+        inputs.extend([row.clone(), row]);
+    }
+
+    fn generate_output(inputs: &[String], enhancement: &[u8]) -> Vec<String> {
+        let mut outputs = vec![];
+
+        for i in 1..inputs.len() - 1 {
+            let mut output = String::new();
+
+            for j in 1..inputs[i].len() - 1 {
+                let mut value: usize = 0;
+                let mut mask: usize = 0x100;
+
+                for fi in i as i32 - 1..=i as i32 + 1 {
+                    let frame_line = if fi < 0 || fi >= inputs.len() as i32 {
+                        inputs[0].as_bytes()
+                    } else {
+                        inputs[fi as usize].as_bytes()
+                    };
+
+                    for fj in j as i32 - 1..=j as i32 + 1 {
+                        if fi >= 0 && fi < inputs.len() as i32 && fj >= 0 && fj < frame_line.len() as i32 && frame_line[fj as usize] == b'#' {
+                            value |= mask;
+                        }
+
+                        mask >>= 1;
+                    }
+                }
+
+                output.push(enhancement[value] as char);
+            }
+
+            outputs.push(output);
+        }
+
+        outputs
+    }
+
+    // Pairs of steps, the first one from a dark background
+    pub fn lit_after(enhancement: &[u8], mut inputs: Vec<String>, steps: usize) -> usize {
+        for _ in 0..steps / 2 {
+            expand(&mut inputs, '.');
+            inputs = generate_output(&inputs, enhancement);
+
+            expand(&mut inputs, enhancement[0] as char);
+            inputs = generate_output(&inputs, enhancement);
+        }
+
+        inputs.iter().map(|input| input.matches('#').count()).sum()
+    }
+}
+
+fn report(name: &str, rounds: usize, stats: &Stats) {
+    println!("{:<10}{:>6} rounds{:>12.3} ms", name, rounds, stats.median / 1e6);
+}
+
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs/day20.txt");
+    let content = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+
+    let (enhancement, pixels) = day20::read_image(&content).unwrap();
+    let image = InfiniteImage::new(pixels);
+    let rule = PairRule::new(enhancement);
+    let rows: Vec<String> = image.pixels().rows().map(|row| row.iter().collect()).collect();
+
+    let lit = (0..50).fold(PackedImage::new(&image), |image, _| image.enhance(&rule)).lit();
+    assert_eq!(Some(strings::lit_after(enhancement, rows.clone(), 50)), lit);
+
+    let strings = bench::time(ITERATIONS, || strings::lit_after(enhancement, rows.clone(), 50));
+
+    let grid = bench::time(ITERATIONS, || {
+        (0..50).fold(image.clone(), |image, _| image.enhance(enhancement)).lit()
+    });
+
+    let packed = bench::time(ITERATIONS, || {
+        (0..50).fold(PackedImage::new(&image), |image, _| image.enhance(&rule)).lit()
+    });
+
+    report("strings", 50, &strings);
+    report("grid", 50, &grid);
+    report("packed", 50, &packed);
+    println!("packed is {:.0} times faster than strings, {:.0} than grid", strings.median / packed.median, grid.median / packed.median);
+
+    // The image grows to 1100 pixels on a side
+    let many = bench::time(ITERATIONS, || {
+        (0..500).fold(PackedImage::new(&image), |image, _| image.enhance(&rule)).lit()
+    });
+
+    report("packed", 500, &many);
+}
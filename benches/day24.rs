@@ -0,0 +1,77 @@
+// Model numbers checked per second by the ALU and by the compiled program,
+// counting down from 99999999999999
+
+use std::fs;
+use std::path::Path;
+
+use aoc2021::bench;
+use aoc2021::day24::{self, Checker, Compiled, ALU};
+
+const BATCH: usize = 100_000;
+const ITERATIONS: usize = 10;
+
+// The next model number below `digits`, none of them is 0
+fn count_down(digits: &mut [i64; 14]) {
+    for d in digits.iter_mut().rev() {
+        if *d > 1 {
+            *d -= 1;
+            return;
+        }
+
+        *d = 9;
+    }
+}
+
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs/day24.txt");
+    let content = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+
+    let instructions = day24::assemble(&content).unwrap();
+    let compiled = Compiled::new(&instructions);
+    let mut alu = ALU::new(instructions.clone());
+
+    println!("{} instructions, {} once compiled", instructions.len(), compiled.steps());
+
+    let mut digits = [9; 14];
+    let alu_stats = bench::time(ITERATIONS, || {
+        let mut accepted = 0;
+
+        for _ in 0..BATCH {
+            alu.reset();
+            accepted += (alu.run(&digits).is_ok() && alu.z == 0) as usize;
+            count_down(&mut digits);
+        }
+
+        accepted
+    });
+
+    let mut digits = [9; 14];
+    let compiled_stats = bench::time(ITERATIONS, || {
+        let mut accepted = 0;
+
+        for _ in 0..BATCH {
+            accepted += compiled.accepts(&digits) as usize;
+            count_down(&mut digits);
+        }
+
+        accepted
+    });
+
+    // Resuming from the last digit that changed
+    let mut checker = Checker::new(&compiled);
+    let mut digits = [9; 14];
+    let checker_stats = bench::time(ITERATIONS, || {
+        let mut accepted = 0;
+
+        for _ in 0..BATCH {
+            accepted += checker.accepts(&digits) as usize;
+            count_down(&mut digits);
+        }
+
+        accepted
+    });
+
+    for (name, stats) in [("alu", alu_stats), ("compiled", compiled_stats), ("checker", checker_stats)] {
+        println!("{:<10}{:>12.0} candidates/s", name, BATCH as f64 / (stats.median / 1e9));
+    }
+}
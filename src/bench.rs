@@ -0,0 +1,277 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use crate::json::Value;
+use crate::parse::ParseError;
+use crate::registry::Day;
+use crate::solution::Logger;
+
+// Timings in nanoseconds
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub min: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarize");
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let mid = sorted.len() / 2;
+        let median =
+            if sorted.len() % 2 == 0 {
+                (sorted[mid - 1] + sorted[mid]) / 2.0
+            } else {
+                sorted[mid]
+            };
+
+        // Sample standard deviation, a single run has none
+        let stddev =
+            if samples.len() > 1 {
+                let sum_sq: f64 = samples.iter().map(|s| (s - mean) * (s - mean)).sum();
+                (sum_sq / (n - 1.0)).sqrt()
+            } else {
+                0.0
+            };
+
+        Stats {
+            mean,
+            median,
+            stddev,
+            min: sorted[0],
+        }
+    }
+}
+
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    let samples: Vec<f64> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed().as_nanos() as f64
+        })
+        .collect();
+
+    Stats::from_samples(&samples)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measure {
+    pub day: String,
+    // "parse", "part1" or "part2"
+    pub stage: String,
+    pub iterations: usize,
+    pub stats: Stats,
+}
+
+// The parts read their own input, so their timings include the parsing
+pub fn bench_day(day: &Day, content: &str, iterations: usize) -> Result<Vec<Measure>, ParseError> {
+    let logger = Logger::silent();
+    let solution = day.solution;
+
+    // A malformed input is reported once instead of being timed
+    solution.parse(content)?;
+
+    let measure = |stage: &str, stats: Stats| Measure {
+        day: day.name.to_string(),
+        stage: stage.to_string(),
+        iterations,
+        stats,
+    };
+
+    let mut measures = vec![
+        measure("parse", time(iterations, || solution.parse(content))),
+        measure("part1", time(iterations, || solution.part1(content, &logger))),
+    ];
+
+    if solution.part2(content, &logger).is_some() {
+        measures.push(measure("part2", time(iterations, || solution.part2(content, &logger))));
+    }
+
+    Ok(measures)
+}
+
+pub fn to_json(measures: &[Measure]) -> Value {
+    let results = measures
+        .iter()
+        .map(|m| {
+            Value::Object(vec![
+                ("day".to_string(), Value::from(m.day.as_str())),
+                ("stage".to_string(), Value::from(m.stage.as_str())),
+                ("iterations".to_string(), Value::from(m.iterations)),
+                ("mean_ns".to_string(), Value::from(m.stats.mean)),
+                ("median_ns".to_string(), Value::from(m.stats.median)),
+                ("stddev_ns".to_string(), Value::from(m.stats.stddev)),
+                ("min_ns".to_string(), Value::from(m.stats.min)),
+            ])
+        })
+        .collect();
+
+    Value::Object(vec![("results".to_string(), Value::Array(results))])
+}
+
+// None when the value isn't a file written by `to_json`
+pub fn from_json(value: &Value) -> Option<Vec<Measure>> {
+    value
+        .get("results")?
+        .as_array()?
+        .iter()
+        .map(|m| {
+            let number = |key: &str| m.get(key).and_then(Value::as_f64);
+
+            Some(Measure {
+                day: m.get("day")?.as_str()?.to_string(),
+                stage: m.get("stage")?.as_str()?.to_string(),
+                iterations: number("iterations")? as usize,
+                stats: Stats {
+                    mean: number("mean_ns")?,
+                    median: number("median_ns")?,
+                    stddev: number("stddev_ns")?,
+                    min: number("min_ns")?,
+                },
+            })
+        })
+        .collect()
+}
+
+// Results of a new run replace the previous ones of the same day and stage,
+// the days that weren't run are kept
+pub fn merge(previous: &[Measure], current: &[Measure]) -> Vec<Measure> {
+    let mut merged: Vec<Measure> = previous
+        .iter()
+        .filter(|p| !current.iter().any(|c| c.day == p.day && c.stage == p.stage))
+        .cloned()
+        .collect();
+
+    merged.extend(current.iter().cloned());
+    merged
+}
+
+// Relative change of the median against the previous run, 0.1 is 10% slower
+pub fn change(previous: &[Measure], current: &Measure) -> Option<f64> {
+    previous
+        .iter()
+        .find(|p| p.day == current.day && p.stage == current.stage && p.stats.median > 0.0)
+        .map(|p| current.stats.median / p.stats.median - 1.0)
+}
+
+pub fn format_ns(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{:.0}ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.2}µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2}ms", ns / 1e6)
+    } else {
+        format!("{:.2}s", ns / 1e9)
+    }
+}
+
+// Table of the measures, returns it with the number of regressions found
+pub fn report(measures: &[Measure], previous: &[Measure], threshold: f64) -> (String, usize) {
+    let mut out = format!(
+        "{:<8} {:<6} {:>10} {:>10} {:>10} {:>10}  {}\n",
+        "day", "stage", "mean", "median", "stddev", "min", "vs previous"
+    );
+
+    let mut regressions = 0;
+
+    for m in measures {
+        let versus = match change(previous, m) {
+            Some(c) if c > threshold => {
+                regressions += 1;
+                format!("{:+.1}% REGRESSION", c * 100.0)
+            },
+            Some(c) => format!("{:+.1}%", c * 100.0),
+            None => "-".to_string(),
+        };
+
+        out.push_str(&format!(
+            "{:<8} {:<6} {:>10} {:>10} {:>10} {:>10}  {}\n",
+            m.day,
+            m.stage,
+            format_ns(m.stats.mean),
+            format_ns(m.stats.median),
+            format_ns(m.stats.stddev),
+            format_ns(m.stats.min),
+            versus
+        ));
+    }
+
+    (out, regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+    use crate::registry;
+
+    fn measure(day: &str, stage: &str, median: f64) -> Measure {
+        Measure {
+            day: day.to_string(),
+            stage: stage.to_string(),
+            iterations: 3,
+            stats: Stats { mean: median, median, stddev: 0.0, min: median },
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(&[4.0, 1.0, 3.0, 2.0]);
+
+        assert_eq!((stats.mean, stats.median, stats.min), (2.5, 2.5, 1.0));
+        assert!((stats.stddev - 1.2909944).abs() < 1e-6);
+
+        let stats = Stats::from_samples(&[7.0]);
+        assert_eq!((stats.median, stats.stddev), (7.0, 0.0));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let measures = vec![measure("day01", "parse", 1500.0), measure("day15v4", "part2", 2.5e9)];
+        let text = to_json(&measures).pretty();
+
+        assert_eq!(from_json(&json::parse(&text).unwrap()), Some(measures));
+        assert_eq!(from_json(&json::parse(r#"{"results": [{"day": 1}]}"#).unwrap()), None);
+    }
+
+    #[test]
+    fn regressions_against_previous_run() {
+        let previous = vec![measure("day01", "part1", 100.0), measure("day02", "part1", 100.0)];
+        let current = vec![
+            measure("day01", "part1", 125.0),
+            measure("day02", "part1", 105.0),
+            measure("day03", "part1", 1.0),
+        ];
+
+        let (table, regressions) = report(&current, &previous, 0.1);
+
+        assert_eq!(regressions, 1);
+        assert!(table.contains("+25.0% REGRESSION"));
+        assert!(table.contains("+5.0%\n"));
+        assert_eq!(change(&previous, &current[2]), None);
+
+        let merged = merge(&previous, &current[..1]);
+        assert_eq!(merged, vec![previous[1].clone(), current[0].clone()]);
+    }
+
+    #[test]
+    fn bench_example() {
+        let day = registry::find(1, None).unwrap();
+
+        let measures = bench_day(day, "199\n200\n208\n210\n", 2).unwrap();
+        let stages: Vec<&str> = measures.iter().map(|m| m.stage.as_str()).collect();
+
+        assert_eq!(stages, ["parse", "part1", "part2"]);
+        assert!(bench_day(day, "199\nx\n", 2).is_err());
+    }
+}
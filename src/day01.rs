@@ -0,0 +1,85 @@
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+fn read_measures(content: &str) -> Result<Vec<i32>, ParseError> {
+    parse::lines(content)
+        .map(|line| line.parse::<i32>(line.text))
+        .collect()
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let measures = read_measures(content)?;
+
+    if measures.len() < 2 {
+        return Err(ParseError::end_of_input(content, "expecting at least two measures"));
+    }
+
+    let mut count = 0;
+
+    for i in 0..measures.len() - 1 {
+        if measures[i + 1] > measures[i] { 
+            count += 1;
+            log!(logger, "(increased)");
+        } else if measures[i + 1] < measures[i] {
+            log!(logger, "(decreased)");
+        } else {
+            log!(logger, "(unchanged)");
+        }
+    }
+
+    log!(logger, "number of increased: {}", count);
+
+    Ok(Answer::from(count))
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let measures = read_measures(content)?;
+
+    // Two windows of three measures to compare
+    if measures.len() < 4 {
+        return Err(ParseError::end_of_input(content, "expecting at least four measures"));
+    }
+
+    let mut windows: Vec<i32> = Vec::new(); //Vec::with_capacity(measures.len() - 2);
+
+    for i in 0..measures.len() - 2 {
+        windows.push(measures[i] + measures[i + 1] + measures[i + 2]);
+    }
+
+    let mut count = 0;
+
+    for i in 0..windows.len() - 1 {
+        if windows[i + 1] > windows[i] { 
+            count += 1;
+        }
+        // if windows[i + 1] > windows[i] { 
+        //     count += 1;
+        //     println!("(increased)");
+        // } else if windows[i + 1] < windows[i] {
+        //     println!("(decreased)");
+        // } else {
+        //     println!("(unchanged)");
+        // }
+    }
+
+    log!(logger, "number of increased: {}", count);
+
+    Ok(Answer::from(count))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_measures(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
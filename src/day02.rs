@@ -0,0 +1,98 @@
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+enum Command {
+    Forward,
+    Up,
+    Down,
+}
+
+fn read_commands(content: &str) -> Result<Vec<(Command, i64)>, ParseError> {
+    parse::lines(content)
+        .map(|line| {
+            let splited = line.fields(" ", 2)?;
+
+            let command = match splited[0] {
+                "forward" => Command::Forward,
+                "up"      => Command::Up,
+                "down"    => Command::Down,
+                other     => return Err(line.error(other, "unknown command")),
+            };
+
+            Ok((command, line.parse::<i64>(splited[1])?))
+        })
+        .collect()
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut pos: i64 = 0;
+    let mut depth: i64 = 0;
+
+    for (command, value) in read_commands(content)? {
+        match command {
+            Command::Forward => pos += value,
+            Command::Up      => depth -= value,
+            Command::Down    => depth += value,
+        }
+    }
+
+    log!(logger, "pos: {}, depth: {}, pos*depth: {}", pos, depth, pos * depth);
+
+    Ok(Answer::from(pos * depth))
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut pos: i64 = 0;
+    let mut aim: i64 = 0;
+    let mut depth: i64 = 0;
+
+    for (command, value) in read_commands(content)? {
+        match command {
+            Command::Forward => {
+                pos += value;
+                depth += aim * value;
+            },
+            Command::Up      => aim -= value,
+            Command::Down    => aim += value,
+        }
+    }
+
+    log!(logger, "pos: {}, depth: {}, aim: {}, pos*depth: {}", pos, depth, aim, pos * depth);
+
+    Ok(Answer::from(pos * depth))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_commands(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_commands() {
+        let logger = Logger::silent();
+
+        let err = part1("forward 5\nbackward 2\n", &logger).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 1, "backward", "unknown command"));
+
+        let err = part2("forward 5\ndown x\n", &logger).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 6, "x", "not a number"));
+
+        assert!(part1("forward\n", &logger).is_err());
+    }
+}
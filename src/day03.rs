@@ -1,7 +1,34 @@
-use std::fs;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
-fn _part1(content: String) {
-    let lines = content.lines().collect::<Vec<&str>>();
+// Lines of the same number of bits, at most 16 so they fit the rates
+fn read_report(content: &str) -> Result<Vec<&str>, ParseError> {
+    let mut lines = vec![];
+
+    for line in parse::lines(content) {
+        if let Some(i) = line.text.find(|c| c != '0' && c != '1') {
+            return Err(line.error_at(i, 1, "not a bit"));
+        }
+
+        let num_bits = lines.first().map_or(line.text.len(), |first: &&str| first.len());
+
+        if line.text.is_empty() || line.text.len() != num_bits || num_bits > 16 {
+            return Err(line.error(line.text, "number of bits differs or is out of 1..=16"));
+        }
+
+        lines.push(line.text);
+    }
+
+    if lines.is_empty() {
+        return Err(ParseError::end_of_input(content, "empty report"));
+    }
+
+    Ok(lines)
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let lines = read_report(content)?;
 
     let num_bits = lines[0].len();
     let mut bits: Vec<i32> = vec![0; num_bits];
@@ -31,10 +58,13 @@ fn _part1(content: String) {
     let epsilon_rate = !gamma_rate & mask;
     let power = (gamma_rate as u32) * (epsilon_rate as u32);
 
-    println!("gamma: {}, epsilon: {}, power: {}", gamma_rate, epsilon_rate, power);
+    log!(logger, "gamma: {}, epsilon: {}, power: {}", gamma_rate, epsilon_rate, power);
+
+    Ok(Answer::from(power))
 }
 
-fn bit_criteria(content: Vec<&str>, num_bits: usize, lead_char: char) -> i32 {
+// The rating of the line left by the criteria, None when no line is left
+fn bit_criteria(content: Vec<&str>, num_bits: usize, lead_char: char) -> Option<i32> {
     let mut current_bit = 0;
 
     let mut filtered = content;
@@ -99,12 +129,13 @@ fn bit_criteria(content: Vec<&str>, num_bits: usize, lead_char: char) -> i32 {
         }
     }
 
-    i32::from_str_radix(filtered[0], 2)
-        .expect("Not a number")
+    filtered
+        .first()
+        .map(|line| i32::from_str_radix(line, 2).expect("Not a number"))
 }
 
-fn part2(content: String) {
-    let lines = content.lines().collect::<Vec<&str>>();
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let lines = read_report(content)?;
 
     let num_bits = lines[0].len();
 
@@ -112,29 +143,39 @@ fn part2(content: String) {
         lines.clone(),
         num_bits,
         '1'
-    );
+    ).ok_or_else(|| ParseError::end_of_input(content, "no number left for the O2 generator rating"))?;
 
     let co2_scrubber_rating = bit_criteria(
         lines.clone(),
         num_bits,
         '0'
-    );
+    ).ok_or_else(|| ParseError::end_of_input(content, "no number left for the CO2 scrubber rating"))?;
 
     let life_support_rating = o2_generator_rating * co2_scrubber_rating;
 
-    println!(
+    log!(
+        logger,
         "O2 gen: {}, CO2 scrubber: {}, life support: {}",
         o2_generator_rating,
         co2_scrubber_rating,
         life_support_rating
     );
+
+    Ok(Answer::from(life_support_rating))
 }
 
-fn main() {
-    let filename = "./inputs/day03-part1.txt";
+pub struct Day03;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+impl Solution for Day03 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_report(content).map(|_| ())
+    }
 
-    part2(content);
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
 }
@@ -1,4 +1,6 @@
-use std::fs;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Completed {
@@ -66,6 +68,7 @@ impl Board {
         Completed::Nothing
     }
 
+    #[allow(dead_code)]
     pub fn sum_completed(&self, c: Completed) -> i32 {
         let mut sum = 0i32;
 
@@ -102,79 +105,79 @@ impl Board {
     }
 }
 
-fn _part1(content: String) {
-    let mut data: Vec<&str> = content.split_terminator("\n\n").collect();
+fn read_game(content: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    let mut lines = parse::lines(content);
 
-    let numbers: Vec<i32> = data[0]
+    let first = parse::first_line(content)?;
+    lines.next();
+
+    let numbers = first.text
         .split(",")
-        .map(|v| {
-            i32::from_str_radix(v, 10)
-                .expect("Not a number")
-        })
-        .collect();
-
-    let mut boards: Vec<Board> = data
-        .drain(1..)
-        .map(|board_str| {
-            let mut values = [[0i32; 5]; 5];
-
-            for (i, row_str) in board_str.split("\n").enumerate() {
-                for (j, num_str) in row_str.split_whitespace().enumerate() {
-                    let num = i32::from_str_radix(num_str, 10)
-                        .expect("Not a number");
-
-                    values[i][j] = num;
-                }
+        .map(|v| first.parse::<i32>(v))
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+
+    let mut boards = vec![];
+    let mut values = [[0i32; 5]; 5];
+    let mut row = 0;
+
+    for line in lines {
+        if line.text.trim().is_empty() {
+            if row != 0 {
+                return Err(line.error(line.text, "board with less than 5 rows"));
             }
 
-            Board::new(values)
-        })
-        .collect();
+            continue;
+        }
+
+        let row_values: Vec<&str> = line.text.split_whitespace().collect();
+
+        if row_values.len() != 5 {
+            return Err(line.error(line.text, "board row without 5 numbers"));
+        }
+
+        for (j, num_str) in row_values.into_iter().enumerate() {
+            values[row][j] = line.parse::<i32>(num_str)?;
+        }
+
+        row += 1;
+
+        if row == 5 {
+            boards.push(Board::new(values));
+            row = 0;
+        }
+    }
+
+    if row != 0 {
+        return Err(ParseError::end_of_input(content, "board with less than 5 rows"));
+    }
 
-    'marking: for &n in numbers.iter() {
+    Ok((numbers, boards))
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (numbers, mut boards) = read_game(content)?;
+
+    for &n in numbers.iter() {
         for (i, b) in boards.iter_mut().enumerate() {
             let completed = b.mark(n);
 
             if completed != Completed::Nothing {
                 let sum = boards[i].sum_umarked();
 
-                println!("result: {}", sum * n);
-                break 'marking;
+                log!(logger, "result: {}", sum * n);
+                return Ok(Answer::from(sum * n));
             }
         }
     }
-}
-
-fn part2(content: String) {
-    let mut data: Vec<&str> = content.split_terminator("\n\n").collect();
 
-    let numbers: Vec<i32> = data[0]
-        .split(",")
-        .map(|v| {
-            i32::from_str_radix(v, 10)
-                .expect("Not a number")
-        })
-        .collect();
-
-    let mut boards: Vec<Board> = data
-        .drain(1..)
-        .map(|board_str| {
-            let mut values = [[0i32; 5]; 5];
-
-            for (i, row_str) in board_str.split("\n").enumerate() {
-                for (j, num_str) in row_str.split_whitespace().enumerate() {
-                    let num = i32::from_str_radix(num_str, 10)
-                        .expect("Not a number");
-
-                    values[i][j] = num;
-                }
-            }
+    Ok(Answer::from(0))
+}
 
-            Board::new(values)
-        })
-        .collect();
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (numbers, mut boards) = read_game(content)?;
 
     let mut win_count = 0;
+    let mut last_result = 0;
 
     for &n in numbers.iter() {
         for (i, b) in boards.iter_mut().enumerate() {
@@ -182,7 +185,8 @@ fn part2(content: String) {
                 if b.mark(n) != Completed::Nothing {
                     let sum = b.sum_umarked();
 
-                    println!("result for {}: {}", i, sum * n);
+                    log!(logger, "result for {}: {}", i, sum * n);
+                    last_result = sum * n;
                     win_count += 1;
                 }
             }
@@ -192,13 +196,22 @@ fn part2(content: String) {
             break;
         }
     }
+
+    Ok(Answer::from(last_result))
 }
 
-fn main() {
-    let filename = "./inputs/day04.txt";
+pub struct Day04;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+impl Solution for Day04 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_game(content).map(|_| ())
+    }
 
-    part2(content);
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
 }
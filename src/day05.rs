@@ -1,6 +1,8 @@
-use std::fs;
 use std::mem;
 
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
 struct Segment<T> {
     x1: T, 
     y1: T, 
@@ -42,37 +44,36 @@ fn max3(a: i32, b: i32, c: i32) -> i32 {
     }
 }
 
-fn part1(content: String) {
+// Lines as "x1,y1 -> x2,y2", the coordinates index the grid so they can't be negative
+fn read_lines(content: &str) -> Result<Vec<[i32; 4]>, ParseError> {
+    parse::lines(content)
+        .map(|line| {
+            let points = line.fields(" -> ", 2)?;
+            let mut coords = [0i32; 4];
+
+            for (i, point) in points.iter().enumerate() {
+                let values = point.split(",").collect::<Vec<&str>>();
+
+                if values.len() != 2 {
+                    return Err(line.error(point, "expecting a point as x,y"));
+                }
+
+                coords[2 * i] = line.parse::<u16>(values[0])? as i32;
+                coords[2 * i + 1] = line.parse::<u16>(values[1])? as i32;
+            }
+
+            Ok(coords)
+        })
+        .collect()
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
     let mut x_max: i32 = -1;
     let mut y_max: i32 = -1;
 
-    let segments: Vec<Segment<i32>> = content
-        .lines()
-        .map(|line| {
-            let points: Vec<&str> = line
-                .split(" -> ")
-                .collect();
-
-            let result: Vec<i32> = points[0]
-                .split(",")
-                .map(|v| {
-                    i32::from_str_radix(v, 10).expect("not a number")
-                })
-                .collect();
-
-            let x1 = result[0];
-            let y1 = result[1];
-
-            let result: Vec<i32> = points[1]
-                .split(",")
-                .map(|v| {
-                    i32::from_str_radix(v, 10).expect("not a number")
-                })
-                .collect();
-
-            let x2 = result[0];
-            let y2 = result[1];
-
+    let segments: Vec<Segment<i32>> = read_lines(content)?
+        .into_iter()
+        .map(|[x1, y1, x2, y2]| {
             x_max = max3(x_max, x1, x2);
             y_max = max3(y_max, y1, y2);
 
@@ -133,40 +134,16 @@ fn part1(content: String) {
         }
     }
 
-    println!("count: {}", count);
+    Ok(Answer::from(count))
 }
 
-fn part2(content: String) {
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
     let mut x_max: i32 = -1;
     let mut y_max: i32 = -1;
 
-    let segments: Vec<Segment<f32>> = content
-        .lines()
-        .map(|line| {
-            let points: Vec<&str> = line
-                .split(" -> ")
-                .collect();
-
-            let result: Vec<i32> = points[0]
-                .split(",")
-                .map(|v| {
-                    i32::from_str_radix(v, 10).expect("not a number")
-                })
-                .collect();
-
-            let x1 = result[0];
-            let y1 = result[1];
-
-            let result: Vec<i32> = points[1]
-                .split(",")
-                .map(|v| {
-                    i32::from_str_radix(v, 10).expect("not a number")
-                })
-                .collect();
-
-            let x2 = result[0];
-            let y2 = result[1];
-
+    let segments: Vec<Segment<f32>> = read_lines(content)?
+        .into_iter()
+        .map(|[x1, y1, x2, y2]| {
             x_max = max3(x_max, x1, x2);
             y_max = max3(y_max, y1, y2);
 
@@ -239,15 +216,21 @@ fn part2(content: String) {
         }
     }
 
-    println!("count: {}", count);
+    Ok(Answer::from(count))
 }
 
-fn main() {
-    let filename = "./inputs/day05.txt";
+pub struct Day05;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+impl Solution for Day05 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_lines(content).map(|_| ())
+    }
 
-    part2(content);
-}
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
 
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
+}
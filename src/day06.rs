@@ -1,17 +1,26 @@
-use std::fs;
-
 use std::sync::mpsc;
 use std::thread;
 
-fn _part1(content: String) {
-    let mut lanternfish_ages: Vec<u8> =
-        content
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+fn read_ages(content: &str) -> Result<Vec<u8>, ParseError> {
+    let line = parse::first_line(content)?;
+
+    line.text
+        .trim_end()
         .split(",")
         .map(|v| {
-            u8::from_str_radix(v.trim_end(), 10)
-                .expect(format!("{} is not a valid number", v).as_str())
+            match line.parse::<u8>(v)? {
+                age @ 0..=8 => Ok(age),
+                _ => Err(line.error(v, "age out of 0..=8")),
+            }
         })
-        .collect();
+        .collect()
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
+    let mut lanternfish_ages: Vec<u8> = read_ages(content)?;
 
     let mut days_count = 0;
 
@@ -41,18 +50,11 @@ fn _part1(content: String) {
         // println!("{}: {:?}", days_count, lanternfish_ages);
     }
 
-    println!("count: {}", lanternfish_ages.len());
+    Ok(Answer::from(lanternfish_ages.len()))
 }
 
-fn part2(content: String) {
-    let lanternfish_ages: Vec<u8> =
-        content
-        .split(",")
-        .map(|v| {
-            u8::from_str_radix(v.trim_end(), 10)
-                .expect(format!("{} is not a valid number", v).as_str())
-        })
-        .collect();
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
+    let lanternfish_ages: Vec<u8> = read_ages(content)?;
 
     // println!("{}: {:?}", days_count, lanternfish_ages);
 
@@ -119,14 +121,21 @@ fn part2(content: String) {
         total_lanternfish_count += generations_count[(v - 1) as usize];
     }
 
-    println!("count: {}", total_lanternfish_count);
+    Ok(Answer::from(total_lanternfish_count))
 }
 
-fn main() {
-    let filename = "./inputs/day06.txt";
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_ages(content).map(|_| ())
+    }
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
 
-    part2(content);
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
 }
@@ -0,0 +1,97 @@
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+fn read_positions(content: &str) -> Result<Vec<i32>, ParseError> {
+    let line = parse::first_line(content)?;
+
+    line.text
+        .trim_end()
+        .split(",")
+        .map(|v| line.parse::<i32>(v))
+        .collect()
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut positions: Vec<i32> = read_positions(content)?;
+
+    positions.sort();
+
+    let dest_position: i32 = 
+        if positions.len() % 2 == 0 {
+            let i = positions.len() / 2;
+
+            (positions[i - 1] + positions[i]) / 2
+        } else {
+            positions[positions.len() / 2]
+        };
+
+    let total_fuel: i32 = positions
+        .iter()
+        .fold(0, move |acc, p| {
+            acc + (p - dest_position).abs()
+        });
+
+    log!(logger, "{}: {} - {:?}", dest_position, total_fuel, positions);
+
+    Ok(Answer::from(total_fuel))
+}
+
+fn triangular(n: i32) -> i32 {
+    n * (n + 1) / 2
+}
+
+/*
+ * sum of 0.5 * (|p - ai| + 1) * |p - ai|
+ * minimizing -> sum of ai = n * (p +/- 0.5)
+ *            -> p = avg +/- 0.5
+ */
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut positions: Vec<i32> = read_positions(content)?;
+
+    positions.sort();
+
+    let sum: i32 = positions
+        .iter()
+        .sum();
+
+    let avg = (sum as f32) / (positions.len() as f32) + 0.5;
+
+    let dest_position = avg.floor() as i32;
+
+    let total_fuel: i32 = positions
+        .iter()
+        .fold(0, move |acc, p| {
+            acc + triangular((p - dest_position).abs())
+        });
+
+    log!(logger, "{}: {}", dest_position, total_fuel);
+
+    let dest_position = avg.floor() as i32 - 1;
+
+    let lower_total_fuel: i32 = positions
+        .iter()
+        .fold(0, move |acc, p| {
+            acc + triangular((p - dest_position).abs())
+        });
+
+    log!(logger, "{}: {}", dest_position, lower_total_fuel);
+
+    Ok(Answer::from(total_fuel.min(lower_total_fuel)))
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_positions(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
@@ -1,19 +1,44 @@
-use std::fs;
 use std::collections::HashMap;
 
 use std::iter::Iterator;
 use std::iter::FromIterator;
 
-fn _part1(content: String) {
-    let total_unique = content
-        .lines()
-        .fold(0, |total, line| {
-            let splits: Vec<&str> = line
-                .split(" | ")
-                .collect();
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
-            let count_unique = splits[1]
-                .split(" ")
+// Line of the entry, its unique patterns and the displayed digits
+type Entry<'a> = (parse::Line<'a>, Vec<&'a str>, Vec<&'a str>);
+
+// Ten unique patterns and the four displayed digits of each line
+fn read_entries(content: &str) -> Result<Vec<Entry<'_>>, ParseError> {
+    parse::lines(content)
+        .map(|line| {
+            let splits = line.fields(" | ", 2)?;
+
+            let patterns: Vec<&str> = splits[0].split(" ").collect();
+            let displayed: Vec<&str> = splits[1].split(" ").collect();
+
+            if patterns.len() != 10 || displayed.len() != 4 {
+                return Err(line.error(line.text, "expecting 10 patterns and 4 digits"));
+            }
+
+            for segments in patterns.iter().chain(displayed.iter()) {
+                if segments.is_empty() || !segments.chars().all(|c| ('a'..='g').contains(&c)) {
+                    return Err(line.error(segments, "segments must be in a..=g"));
+                }
+            }
+
+            Ok((line, patterns, displayed))
+        })
+        .collect()
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
+    let total_unique = read_entries(content)?
+        .iter()
+        .fold(0, |total, (_, _, displayed)| {
+            let count_unique = displayed
+                .iter()
                 .fold(0, |acc, n| {
                     match n.len() {
                         2 |3 | 4 | 7 => acc + 1,
@@ -24,7 +49,7 @@ fn _part1(content: String) {
             total + count_unique
         });
 
-    println!("total: {}", total_unique);
+    Ok(Answer::from(total_unique))
 }
 
 // Number of chars in 'a' but not in 'b'
@@ -56,20 +81,12 @@ fn sort_chars(s: &str) -> String {
     String::from_iter(chars)
 }
 
-fn part2(content: String) {
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
     let mut result = 0;
 
-    for line in content.lines() {
+    for (line, mut encoded, displayed) in read_entries(content)? {
         let mut map: HashMap<String, i32> = HashMap::new();
 
-        let splits: Vec<&str> = line
-            .split(" | ")
-            .collect();
-
-        let mut encoded: Vec<&str> = splits[0]
-            .split(" ")
-            .collect();
-
         encoded.sort_by(|a, b| {
             a.len().cmp(&(b.len()))
         });
@@ -117,15 +134,12 @@ fn part2(content: String) {
 
         // println!("\n----  ----\n");
 
-        let displayed: Vec<&str> = splits[1]
-            .split(" ")
-            .collect();
-
         let mut value = 0; 
 
         for n in displayed.iter() {
             let sorted = sort_chars(n);
-            let v = map.get(&sorted).unwrap();
+            let v = map.get(&sorted)
+                .ok_or_else(|| line.error(n, "digit doesn't match any pattern"))?;
 
             value = 10 * value + v;
         }
@@ -135,14 +149,22 @@ fn part2(content: String) {
         result += value;
     }
 
-    println!("result: {}", result);
+    Ok(Answer::from(result))
 }
   
-fn main() {
-    let filename = "./inputs/day08.txt";
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_entries(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
 
-    part2(content);
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
 }
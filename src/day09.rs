@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use crate::grid::{Grid, Pos};
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+fn is_low_point(values: &Grid<u32>, pos: Pos) -> bool {
+    values
+        .neighbors4(pos)
+        .all(|n| values[pos] < values[n])
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
+    let values = parse::digit_grid(content)?;
+
+    let low_points: Vec<u32> = values
+        .positions()
+        .filter(|&pos| is_low_point(&values, pos))
+        .map(|pos| values[pos])
+        .collect();
+
+    let sum_of_risk_levels = low_points
+        .iter()
+        .fold(0, |acc, v| acc + v + 1);
+
+    Ok(Answer::from(sum_of_risk_levels))
+}
+
+fn basin_walk(values: &Grid<u32>, pts: &mut HashSet<Pos>, pos: Pos) {
+    if values[pos] >= 9 {
+        return;
+    }
+
+    pts.insert(pos);
+
+    for n in values.neighbors4(pos) {
+        if values[pos] < values[n] {
+            basin_walk(values, pts, n);
+        }
+    }
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let values = parse::digit_grid(content)?;
+
+    let mut basin_sizes: Vec<usize> = vec![];
+
+    for pos in values.positions() {
+        if is_low_point(&values, pos) {
+            let mut points: HashSet<Pos> = HashSet::new();
+            basin_walk(&values, &mut points, pos);
+            basin_sizes.push(points.len());
+        }
+    }
+
+    if basin_sizes.len() < 3 {
+        return Err(ParseError::end_of_input(content, "fewer than three basins"));
+    }
+
+    basin_sizes.sort();
+
+    let mut result = 1;
+
+    let v = basin_sizes.pop().unwrap();
+    result *= v;
+
+    log!(logger, ":: {}", v);
+
+    let v = basin_sizes.pop().unwrap();
+    result *= v;
+
+    log!(logger, ":: {}", v);
+
+    let v = basin_sizes.pop().unwrap();
+    result *= v;
+
+    log!(logger, ":: {}", v);
+
+    log!(logger, "\nresult {}", result);
+
+    Ok(Answer::from(result))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        parse::digit_grid(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
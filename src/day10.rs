@@ -1,6 +1,7 @@
-use std::fs;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
-#[allow(dead_code)]
 fn lookup_for_checker_points(c: char) -> i32 {
     match c {
     ')' => 3,
@@ -31,14 +32,24 @@ fn get_closing(c: char) -> char {
     }
 }
 
-#[allow(dead_code)]
-fn part1(content: String) {
+fn read_chunks(content: &str) -> Result<Vec<parse::Line<'_>>, ParseError> {
+    parse::lines(content)
+        .map(|line| {
+            match line.text.char_indices().find(|&(_, c)| !"()[]{}<>".contains(c)) {
+                Some((i, c)) => Err(line.error_at(i, c.len_utf8(), "not a bracket")),
+                None => Ok(line),
+            }
+        })
+        .collect()
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
     let mut total_points = 0i32;
 
-    for (i, line) in content.lines().enumerate() {
+    for (i, line) in read_chunks(content)?.into_iter().enumerate() {
         let mut stack: Vec<char> = vec![];
 
-        'inner: for (j, c) in line.chars().enumerate() {
+        'inner: for (j, c) in line.text.chars().enumerate() {
             match c {
             '(' | '{' | '[' | '<' => {
                 stack.push(c);
@@ -46,7 +57,8 @@ fn part1(content: String) {
             ')' | '}' | ']' | '>' => {
                 if let Some(&l) = stack.last() {
                     if get_closing(l) != c {
-                        println!(
+                        log!(
+                            logger,
                             "{}:{} - Expected '{}', but found '{}' instead",
                             i, j, get_closing(l), c
                         );
@@ -58,29 +70,30 @@ fn part1(content: String) {
                         stack.pop();
                     }
                 } else {
-                    println!(
+                    log!(
+                        logger,
                         "{}:{} - Expected nothing, but found '{}' instead",
                         i, j, c
                     );
                     break 'inner;
                 }
             },
-            _ => unreachable!(),
+            _ => unreachable!("rejected by read_chunks"),
             }
         }
     }
 
-    println!("points: {}", total_points);
+    Ok(Answer::from(total_points))
 }
 
-fn part2(content: String) {
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
     let mut totals: Vec<u64> = Vec::new();
 
-    for line in content.lines() {
+    for line in read_chunks(content)? {
         let mut stack: Vec<char> = vec![];
         let mut is_corrupted = false;
 
-        'inner: for c in line.chars() {
+        'inner: for c in line.text.chars() {
             match c {
             '(' | '{' | '[' | '<' => {
                 stack.push(c);
@@ -98,7 +111,7 @@ fn part2(content: String) {
                     break 'inner;
                 }
             },
-            _ => unreachable!(),
+            _ => unreachable!("rejected by read_chunks"),
             }
         }
 
@@ -116,18 +129,28 @@ fn part2(content: String) {
         }
     }
 
+    if totals.is_empty() {
+        return Err(ParseError::end_of_input(content, "no incomplete lines"));
+    }
+
     totals.sort();
     let total_points = totals[totals.len() / 2];
 
-    println!("points: {}", total_points);
+    Ok(Answer::from(total_points))
 }
 
-fn main() {
-    let filename = "./inputs/day10.txt";
+pub struct Day10;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not open file");
+impl Solution for Day10 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_chunks(content).map(|_| ())
+    }
 
-    part2(content);
-}
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
 
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
+}
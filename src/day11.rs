@@ -0,0 +1,146 @@
+use std::fmt;
+
+use crate::grid::{Grid, Pos};
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+struct Cell {
+    value: i32,
+    flashed: bool,
+}
+
+impl Cell {
+    fn new(value: i32) -> Self {
+        Cell {
+            value,
+            flashed: false,
+        }
+    }
+}
+
+// Octopuses that flashed this step show as '*'
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.flashed {
+            write!(f, "*")
+        } else {
+            write!(f, "{}", self.value)
+        }
+    }
+}
+
+fn read_grid(content: &str) -> Result<Grid<Cell>, ParseError> {
+    Ok(parse::digit_grid(content)?.map(|&d| Cell::new(d as i32)))
+}
+
+fn print_grid_flashes(grid: &Grid<Cell>, logger: &Logger) {
+    log!(logger, "{}", grid);
+    log!(logger, " --- ");
+}
+
+fn count_and_reset_grid_flashes(grid: &mut Grid<Cell>) -> usize {
+    let mut total = 0;
+
+    for c in grid.values_mut() {
+        if c.flashed {
+            total += 1;
+            c.flashed = false;
+        }
+    }
+
+    total
+}
+
+fn walk_cell(grid: &mut Grid<Cell>, pos: Pos) {
+    let neighbors: Vec<Pos> = grid.neighbors8(pos).collect();
+
+    for n in neighbors {
+        if !grid[n].flashed {
+            if grid[n].value == 9 {
+                grid[n].value = 0;
+                grid[n].flashed = true;
+
+                walk_cell(grid, n);
+            } else {
+                grid[n].value += 1;
+            }
+        }
+    }
+}
+
+// Energy level increase of a step, with the flashes it causes
+fn step(grid: &mut Grid<Cell>) {
+    for pos in grid.positions() {
+        if !grid[pos].flashed {
+            if grid[pos].value == 9 {
+                grid[pos].value = 0;
+                grid[pos].flashed = true;
+
+                walk_cell(grid, pos);
+            } else {
+                grid[pos].value += 1;
+            }
+        }
+    }
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let nsteps = 100;
+
+    let mut grid = read_grid(content)?;
+
+    print_grid_flashes(&grid, logger);
+
+    let mut flash_count = 0;
+
+    for _ in 0..nsteps {
+        step(&mut grid);
+
+        // print_grid_flashes(&grid, logger);
+        flash_count += count_and_reset_grid_flashes(&mut grid);
+    }
+
+    log!(logger, "n# flashes = {}", flash_count);
+
+    Ok(Answer::from(flash_count))
+}
+
+fn is_all_flashed(grid: &Grid<Cell>) -> bool {
+    grid.values().all(|c| c.flashed)
+}
+
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
+    let mut grid = read_grid(content)?;
+
+    let mut nsteps = 0;
+
+    loop {
+        step(&mut grid);
+
+        nsteps += 1;
+        if is_all_flashed(&grid) {
+            break;
+        }
+
+        count_and_reset_grid_flashes(&mut grid);
+    }
+
+    Ok(Answer::from(nsteps))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        parse::digit_grid(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
+}
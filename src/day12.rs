@@ -1,4 +1,5 @@
-use std::fs;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
 #[derive(PartialEq, Eq)]
 enum CaveType {
@@ -203,61 +204,139 @@ impl State {
             print!("\n");
         }
     }
-
-    fn print_total_paths(&self) {
-        println!("paths: {}", self.paths.len());
-    }
 }
 
-#[allow(dead_code)]
-fn part1(content: String) {
-    let paths: Vec<(&str, &str)> = content
-        .lines()
+fn read_paths(content: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    let paths = parse::lines(content)
         .map(|line| {
-            let splited = line
-                .split('-')
-                .collect::<Vec<&str>>();
+            let splited = line.fields("-", 2)?;
+
+            for name in splited.iter() {
+                if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+                    return Err(line.error(name, "not a cave name"));
+                }
+            }
 
-            (splited[0], splited[1])
+            Ok((splited[0], splited[1]))
         })
-        .collect();
+        .collect::<Result<Vec<(&str, &str)>, ParseError>>()?;
+
+    for cave in ["start", "end"] {
+        if !paths.iter().any(|&(s, e)| s == cave || e == cave) {
+            return Err(ParseError::end_of_input(content, &format!("missing the {} cave", cave)));
+        }
+    }
+
+    Ok(paths)
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
+    let paths: Vec<(&str, &str)> = read_paths(content)?;
 
     let mut state = State::new(1);
 
     state.init(&paths);
 
     // state.print_paths();
-    state.print_total_paths();
+    Ok(Answer::from(state.paths.len()))
 }
 
-fn part2(content: String) {
-    let paths: Vec<(&str, &str)> = content
-        .lines()
-        .map(|line| {
-            let splited = line
-                .split('-')
-                .collect::<Vec<&str>>();
-
-            (splited[0], splited[1])
-        })
-        .collect();
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
+    let paths: Vec<(&str, &str)> = read_paths(content)?;
 
     let mut state = State::new(2);
 
     state.init(&paths);
 
     // state.print_paths();
-    state.print_total_paths();
+    Ok(Answer::from(state.paths.len()))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_paths(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
+
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
 }
 
-fn main() {
-    // let filename = "./inputs/day12-example1.txt";
-    // let filename = "./inputs/day12-example2.txt";
-    // let filename = "./inputs/day12-example3.txt";
-    let filename = "./inputs/day12.txt";
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+    // inputs/day12-example.txt
+    const EXAMPLE: &[(&str, &str)] = &[
+        ("start", "A"),
+        ("start", "b"),
+        ("A", "c"),
+        ("A", "b"),
+        ("b", "d"),
+        ("A", "end"),
+        ("b", "end"),
+    ];
 
-    part2(content);
+    fn explore(max_visit_count_once: usize) -> State {
+        let mut state = State::new(max_visit_count_once);
+
+        state.init(&EXAMPLE.to_vec());
+
+        state
+    }
+
+    // How many times each small cave shows up in a path
+    fn small_visits(state: &State, path: &Vec<usize>) -> Vec<usize> {
+        state.caves
+            .iter()
+            .filter(|cave| cave.is_small())
+            .map(|cave| path.iter().filter(|&&id| id == cave.id).count())
+            .collect()
+    }
+
+    #[test]
+    fn small_caves_are_visited_at_most_once() {
+        let state = explore(1);
+
+        assert_eq!(state.paths.len(), 10);
+
+        for path in state.paths.iter() {
+            assert!(small_visits(&state, path).iter().all(|&n| n <= 1));
+        }
+    }
+
+    #[test]
+    fn a_single_small_cave_can_be_visited_twice() {
+        let state = explore(2);
+
+        assert_eq!(state.paths.len(), 36);
+
+        for path in state.paths.iter() {
+            let visits = small_visits(&state, path);
+
+            assert!(visits.iter().all(|&n| n <= 2));
+            assert!(visits.iter().filter(|&&n| n == 2).count() <= 1);
+
+            assert_eq!(path.first(), Some(&state.start_idx));
+            assert_eq!(path.iter().filter(|&&id| id == state.start_idx).count(), 1);
+            assert_eq!(path.last(), Some(&state.end_idx));
+        }
+    }
+
+    #[test]
+    fn paths_are_not_repeated() {
+        let state = explore(2);
+
+        let mut paths = state.paths.clone();
+        paths.sort();
+        paths.dedup();
+
+        assert_eq!(paths.len(), state.paths.len());
+    }
 }
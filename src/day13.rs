@@ -1,4 +1,6 @@
-use std::fs;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
 struct Point {
     x: usize,
@@ -10,8 +12,7 @@ enum FoldType {
     Horizontal(usize),
 }
 
-#[allow(dead_code)]
-fn print_points(points: &Vec<Point>, max_point: &Point) {
+fn render_points(points: &Vec<Point>, max_point: &Point) -> Vec<String> {
     let mut mat: Vec<Vec<char>> = vec![];
 
     for _i in 0..=max_point.y {
@@ -30,13 +31,11 @@ fn print_points(points: &Vec<Point>, max_point: &Point) {
         mat[p.y][p.x] = '#';
     }
 
-    for i in 0..max_point.y {
-        for j in 0..max_point.x {
-            print!("{}", mat[i][j]);
-        }
-        print!("\n");
-    }
-
+    mat
+        .iter()
+        .take(max_point.y)
+        .map(|line| line.iter().take(max_point.x).collect())
+        .collect()
 }
 
 fn remove_duplicated(points: &mut Vec<Point>) {
@@ -125,63 +124,49 @@ fn x_fold(points: &mut Vec<Point>, x: usize, max_point: &mut Point) {
     remove_duplicated(points);
 }
 
-fn part1(content: String) {
-    let lines = content
-        .lines()
-        .collect::<Vec<&str>>();
+// Dots as "x,y", an empty line and then the "fold along x=N" instructions
+fn read_instructions(content: &str) -> Result<(Vec<Point>, Vec<FoldType>), ParseError> {
+    let mut lines = parse::lines(content);
 
-    let empty_line_pos = lines
-        .iter()
-        .position(|v| v.is_empty())
-        .expect("Empty line not found.");
-
-    // let mut remaining_lines = lines.drain(..empty_line_pos);
+    let mut points: Vec<Point> = vec![];
+    let mut found_empty_line = false;
 
-    let mut points: Vec<Point> = lines
-        .iter()
-        .enumerate()
-        .filter(|&(i, _)| i < empty_line_pos)
-        .map(|(_, line)| {
-            let splited = line
-                .split(',')
-                .collect::<Vec<&str>>();
+    for line in lines.by_ref() {
+        if line.text.is_empty() {
+            found_empty_line = true;
+            break;
+        }
 
-            let x = usize::from_str_radix(splited[0], 10)
-                .expect("Not a valid number");
+        let splited = line.fields(",", 2)?;
 
-            let y = usize::from_str_radix(splited[1], 10)
-                .expect("Not a valid number");
+        points.push(Point {
+            x: line.parse::<usize>(splited[0])?,
+            y: line.parse::<usize>(splited[1])?,
+        });
+    }
 
-            Point {
-                x, y
-            }
-        })
-        .collect();
+    if !found_empty_line {
+        return Err(ParseError::end_of_input(content, "empty line not found"));
+    }
 
     let folds: Vec<FoldType> = lines
-        .iter()
-        .enumerate()
-        .filter(|&(i, _)| i > empty_line_pos)
-        .map(|(_, line)| {
-            if line.starts_with("fold along x=") {
-                let value = line.strip_prefix("fold along x=")
-                    .expect("Wrong prefix");
-
-                let x = usize::from_str_radix(value, 10)
-                    .expect("Not a valid number");
-
-                FoldType::Vertical(x)
+        .map(|line| {
+            if let Some(value) = line.text.strip_prefix("fold along x=") {
+                Ok(FoldType::Vertical(line.parse::<usize>(value)?))
+            } else if let Some(value) = line.text.strip_prefix("fold along y=") {
+                Ok(FoldType::Horizontal(line.parse::<usize>(value)?))
             } else {
-                let value = line.strip_prefix("fold along y=")
-                    .expect("Wrong prefix");
-
-                let y = usize::from_str_radix(value, 10)
-                    .expect("Not a valid number");
-
-                FoldType::Horizontal(y)
+                Err(line.error(line.text, "not a fold instruction"))
             }
         })
-        .collect();
+        .collect::<Result<Vec<FoldType>, ParseError>>()?;
+
+    Ok((points, folds))
+}
+
+// Applies at most `max_folds` of the folding instructions
+fn fold_paper(content: &str, max_folds: usize, logger: &Logger) -> Result<(Vec<Point>, Point), ParseError> {
+    let (mut points, folds) = read_instructions(content)?;
 
     let mut max_point = Point { x: 0, y: 0 };
 
@@ -195,37 +180,54 @@ fn part1(content: String) {
         }
     }
 
-    // print_points(&points, &max_point);
+    // render_points(&points, &max_point);
 
-    for fold in folds {
+    for fold in folds.into_iter().take(max_folds) {
         match fold {
             FoldType::Horizontal(y) => {
-                println!("y fold in {}", y);
+                log!(logger, "y fold in {}", y);
                 y_fold(&mut points, y, &mut max_point);
             },
             FoldType::Vertical(x) => {
-                println!("x fold in {}", x);
+                log!(logger, "x fold in {}", x);
                 x_fold(&mut points, x, &mut max_point);
             },
         }
 
         // println!("\n*************\n");
 
-        // print_points(&points, &max_point);
+        // render_points(&points, &max_point);
     }
 
+    log!(logger, "count: {}", points.len());
 
-    println!("\n*************\n");
-    print_points(&points, &max_point);
-    println!("\ncount: {}", points.len());
+    Ok((points, max_point))
 }
 
-fn main() {
-    // let filename = "./inputs/day13.txt";
-    let filename = "./inputs/day13.txt";
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (points, _) = fold_paper(content, 1, logger)?;
+
+    Ok(Answer::from(points.len()))
+}
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (points, max_point) = fold_paper(content, usize::MAX, logger)?;
 
-    part1(content);
+    Ok(Answer::Grid(render_points(&points, &max_point)))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_instructions(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
 }
@@ -1,34 +1,78 @@
-use std::fs;
 use std::collections::HashMap;
 
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
 struct Letter {
     value: char,
     count: usize,
 }
 
-#[allow(dead_code)]
-fn part1(content: String, iterations: usize) {
-    let mut lines = content
-        .lines()
-        .collect::<Vec<&str>>();
+// Template, an empty line and the insertion rules as "AB -> C". Every pair of
+// elements needs a rule, the counting of part 2 relies on it.
+fn read_polymer(content: &str) -> Result<(String, HashMap<String, String>), ParseError> {
+    let is_element = |s: &str| s.chars().all(|c| c.is_ascii_uppercase());
+
+    let mut lines = parse::lines(content);
 
-    let mut input = String::from(lines[0]);
+    let template = parse::first_line(content)?;
+    lines.next();
+
+    if template.text.len() < 2 || !is_element(template.text) {
+        return Err(template.error(template.text, "template must have at least two elements"));
+    }
 
-    lines.drain(..2);
+    match lines.next() {
+        Some(line) if line.text.is_empty() => (),
+        Some(line) => return Err(line.error(line.text, "expecting an empty line")),
+        None => return Err(ParseError::end_of_input(content, "missing insertion rules")),
+    }
 
     let mut pairs = HashMap::new();
 
-    for line in lines.iter() {
-        let splited: Vec<&str> = line
-            .split(" -> ")
-            .collect();
+    for line in lines {
+        let splited = line.fields(" -> ", 2)?;
+
+        if splited[0].len() != 2 || !is_element(splited[0]) {
+            return Err(line.error(splited[0], "not a pair of elements"));
+        }
+
+        if splited[1].len() != 1 || !is_element(splited[1]) {
+            return Err(line.error(splited[1], "not an element"));
+        }
+
+        pairs.insert(splited[0].to_string(), splited[1].to_string());
+    }
+
+    let mut elements: Vec<char> = template.text.chars().collect();
 
-        pairs.insert(
-            splited[0].to_string(),
-            splited[1].to_string()
-        );
+    for (pair, element) in pairs.iter() {
+        elements.extend(pair.chars().chain(element.chars()));
     }
 
+    elements.sort();
+    elements.dedup();
+
+    for &a in elements.iter() {
+        for &b in elements.iter() {
+            let pair = format!("{}{}", a, b);
+
+            if !pairs.contains_key(&pair) {
+                let reason = format!("no insertion rule for {}", pair);
+                return Err(ParseError::end_of_input(content, &reason));
+            }
+        }
+    }
+
+    Ok((template.text.to_string(), pairs))
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let iterations = 10;
+
+    let (mut input, pairs) = read_polymer(content)?;
+
     for _ in 0..iterations {
         let mut result = String::new();
 
@@ -51,7 +95,7 @@ fn part1(content: String, iterations: usize) {
 
         input = result;
 
-        println!("---> {}", input);
+        log!(logger, "---> {}", input);
     }
 
     let mut letters: Vec<Letter> = vec![];
@@ -89,7 +133,9 @@ fn part1(content: String, iterations: usize) {
             None => 0,
         };
 
-    println!("min: {}, max: {}, diff: {}", min_count, max_count, max_count - min_count);
+    log!(logger, "min: {}, max: {}, diff: {}", min_count, max_count, max_count - min_count);
+
+    Ok(Answer::from(max_count - min_count))
 }
 
 struct State {
@@ -98,20 +144,7 @@ struct State {
 }
 
 impl State {
-    fn new(lines: Vec<&str>) -> State {
-        let mut pairs = HashMap::new();
-
-        for line in lines.iter() {
-            let splited: Vec<&str> = line
-                .split(" -> ")
-                .collect();
-
-            pairs.insert(
-                splited[0].to_string(),
-                splited[1].to_string()
-            );
-        }
-
+    fn new(pairs: HashMap<String, String>) -> State {
         State {
             pairs,
             iters: vec![],
@@ -171,7 +204,7 @@ impl State {
         self.iters.push((v, max_iter, rule_counting));
     }
 
-    fn process(&mut self, input: String) {
+    fn process(&mut self, input: String) -> usize {
         let mut counting: HashMap<String, usize> = HashMap::new();
 
         for i in 0..input.len() - 1 {
@@ -211,30 +244,30 @@ impl State {
             }
         }
 
-        println!("min: {}, max: {}, diff: {}", min_value, max_value, max_value - min_value);
+        max_value - min_value
     }
 }
 
-fn part2(content: String) {
-    let mut lines = content
-        .lines()
-        .collect::<Vec<&str>>();
-
-    let input = String::from(lines[0]);
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
+    let (input, pairs) = read_polymer(content)?;
 
-    lines.drain(..2);
+    let mut state = State::new(pairs);
 
-    let mut state = State::new(lines);
-
-    state.process(input);
+    Ok(Answer::from(state.process(input)))
 }
 
-fn main() {
-    // let filename = "./inputs/day14-example.txt";
-    let filename = "./inputs/day14.txt";
+pub struct Day14;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file");
+impl Solution for Day14 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_polymer(content).map(|_| ())
+    }
 
-    part2(content);
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
 }
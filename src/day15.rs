@@ -0,0 +1,160 @@
+use crate::grid::{Grid, Pos};
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+#[derive(Copy, Clone, Debug)]
+enum Dir {
+    Down,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Cell {
+    value: u32,
+
+    lower_path_cost: u32,
+    lower_path_dir: Dir,
+}
+
+fn new_cell(value: u32) -> Cell {
+    Cell {
+        value,
+        lower_path_cost: 1e6 as u32,
+        lower_path_dir: Dir::Right,
+    }
+}
+
+// Cheapest of the cell's right and down neighbors, when cheaper than the
+// path known for the cell
+fn update_cell(grid: &mut Grid<Cell>, pos: Pos) {
+    let (i, j) = pos;
+
+    let (mut lower_cost, mut lower_dir) = (grid[pos].lower_path_cost, grid[pos].lower_path_dir);
+
+    let mut changed = false;
+
+    if let Some(right_cell) = grid.get((i, j + 1)) {
+        if right_cell.lower_path_cost < lower_cost {
+            lower_cost = right_cell.lower_path_cost;
+            lower_dir = Dir::Right;
+
+            changed = true;
+        }
+    }
+
+    if let Some(down_cell) = grid.get((i + 1, j)) {
+        if down_cell.lower_path_cost < lower_cost {
+            lower_cost = down_cell.lower_path_cost;
+            lower_dir = Dir::Down;
+
+            changed = true;
+        }
+    }
+
+    if changed {
+        let cell = &mut grid[pos];
+
+        cell.lower_path_cost = cell.value + lower_cost;
+        cell.lower_path_dir = lower_dir;
+    }
+}
+
+fn update_costs(grid: &mut Grid<Cell>) {
+    let nrows = grid.nrows();
+    let ncols = grid.ncols();
+
+    let mut row_idx: i32 = nrows as i32 - 1;
+    let mut col_idx: i32 = ncols as i32 - 1;
+
+    {
+        let cell = &mut grid[(nrows - 1, ncols - 1)];
+        cell.lower_path_cost = cell.value;
+    }
+
+    while row_idx > -1 || col_idx > -1 {
+        if row_idx > -1 {
+            for j in (0..ncols).rev() {
+                update_cell(grid, (row_idx as usize, j));
+            }
+
+            row_idx -= 1;
+        }
+
+        if col_idx > -1 {
+            for i in (0..nrows).rev() {
+                update_cell(grid, (i, col_idx as usize));
+            }
+
+            col_idx -= 1;
+        }
+    }
+}
+
+// The starting cell is never entered, so its risk is not counted
+fn min_cost(grid: &Grid<Cell>) -> u32 {
+    let cell = &grid[(0, 0)];
+
+    cell.lower_path_cost - cell.value
+}
+
+fn print_path(grid: &Grid<Cell>, logger: &Logger) {
+    let mut path: Vec<Pos> = vec![];
+
+    let mut pos = (0, 0);
+
+    while let Some(cell) = grid.get(pos) {
+        path.push(pos);
+        match cell.lower_path_dir {
+            Dir::Right => pos.1 += 1,
+            Dir::Down => pos.0 += 1,
+        }
+    }
+
+    log!(logger, "{:?}", path);
+
+    for (i, row) in grid.rows().enumerate() {
+        let mut line = String::new();
+
+        for (j, cell) in row.iter().enumerate() {
+            let v = cell.value;
+
+            if path.contains(&(i, j)) {
+                //print!("\\033[31m{}\\033[39m", v);
+                // print!("\\e[1;96;127m{}\\e[0m", v);
+                line.push_str(&format!("\x1B[31m{}\x1B[0m", v));
+
+            } else {
+                line.push_str(&v.to_string());
+            }
+        }
+
+        log!(logger, "{}", line);
+    }
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut grid = parse::digit_grid(content)?.map(|&value| new_cell(value));
+
+    log!(logger, "nrows: {}, ncols: {}", grid.nrows(), grid.ncols());
+
+    update_costs(&mut grid);
+
+    log!(logger, "{:?}", grid[(0, 0)]);
+
+    print_path(&grid, logger);
+
+    Ok(Answer::from(min_cost(&grid)))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        parse::digit_grid(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt::Write;
+
+use crate::grid::{Grid, Pos};
+use crate::image::{self, Image};
+use crate::log;
+use crate::parse::ParseError;
+use crate::search::{self, Exploration, Graph, Path};
+use crate::solution::{Answer, Export, Logger, Solution};
+
+// Entering a cell costs its risk level
+struct Cave<'a> {
+    risks: &'a Grid<u32>,
+    end: Pos,
+}
+
+impl<'a> Cave<'a> {
+    fn new(risks: &'a Grid<u32>) -> Self {
+        Cave {
+            risks,
+            end: (risks.nrows() - 1, risks.ncols() - 1),
+        }
+    }
+}
+
+impl Graph for Cave<'_> {
+    type Node = Pos;
+
+    fn neighbors(&self, pos: &Pos) -> Vec<(Pos, u64)> {
+        self.risks
+            .neighbors4(*pos)
+            .map(|n| (n, self.risks[n] as u64))
+            .collect()
+    }
+
+    // Every cell costs at least 1
+    fn heuristic(&self, pos: &Pos) -> u64 {
+        (self.end.0.abs_diff(pos.0) + self.end.1.abs_diff(pos.1)) as u64
+    }
+}
+
+// Risk levels go from 1 to 9, a cell that costs nothing would break the
+// heuristic and the wrap of the full map
+fn read_risks(content: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(content, |c| c.to_digit(10).filter(|&d| d > 0), "not a risk level from 1 to 9")
+}
+
+// Lowest risk path from the top left cell to the bottom right one
+fn lowest_risk_path(risks: &Grid<u32>, logger: &Logger) -> Path<Pos> {
+    let cave = Cave::new(risks);
+
+    let path = search::astar(&cave, (0, 0), |&pos| pos == cave.end)
+        .expect("the cells of a grid are all connected");
+
+    log!(logger, "final path: {:?}", path.nodes);
+
+    path
+}
+
+fn print_path(risks: &Grid<u32>, path: &Path<Pos>, logger: &Logger) {
+    for (i, row) in risks.rows().enumerate() {
+        let mut line = String::new();
+
+        for (j, v) in row.iter().enumerate() {
+            if path.nodes.contains(&(i, j)) {
+                //print!("\\033[31m{}\\033[39m", v);
+                // print!("\\e[1;96;127m{}\\e[0m", v);
+                line.push_str(&format!("\x1B[31m{}\x1B[0m", v));
+
+            } else {
+                line.push_str(&v.to_string());
+            }
+        }
+
+        log!(logger, "{}", line);
+    }
+
+    log!(logger, "min cost: {}", path.cost);
+}
+
+// The map is 5x5 copies of the tile, each copy to the right or down adds 1
+// to the risk, wrapping from 9 back to 1
+fn full_map(tile: &Grid<u32>) -> Grid<u32> {
+    let nrows = tile.nrows();
+    let ncols = tile.ncols();
+
+    Grid::from_fn(5 * nrows, 5 * ncols, |(i, j)| {
+        let value = tile[(i % nrows, j % ncols)] + (i / nrows + j / ncols) as u32;
+
+        (value - 1) % 9 + 1
+    })
+}
+
+// Cells colored by their cost from the start, from blue for the cheapest to
+// red, the cells the search never reached are black
+fn cost_heatmap(risks: &Grid<u32>, exploration: &Exploration<Pos>) -> Image {
+    let mut heatmap = Image::new(risks.ncols(), risks.nrows(), [0, 0, 0]);
+
+    let max_cost = exploration.visited.iter().map(|&(_, cost)| cost).max().unwrap_or(0).max(1);
+
+    for &((i, j), cost) in &exploration.visited {
+        heatmap.set(j, i, image::heat_color(cost as f64 / max_cost as f64));
+    }
+
+    heatmap
+}
+
+// The heatmap with the path drawn over it
+fn path_svg(heatmap: &Image, path: &Path<Pos>) -> String {
+    let (width, height) = (heatmap.width, heatmap.height);
+
+    // Small maps are scaled up to stay readable
+    let scale = (800 / width.max(height)).max(1);
+
+    let points: Vec<String> = path.nodes
+        .iter()
+        .map(|(i, j)| format!("{}.5,{}.5", j, i))
+        .collect();
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        width * scale, height * scale, width, height
+    );
+    let _ = writeln!(svg, "  <title>lowest total risk: {}</title>", path.cost);
+    let _ = writeln!(
+        svg,
+        r#"  <image width="{}" height="{}" style="image-rendering: pixelated" href="{}"/>"#,
+        width, height, heatmap.to_data_uri()
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <polyline fill="none" stroke="white" stroke-width="0.4" stroke-linejoin="round" points="{}"/>"#,
+        points.join(" ")
+    );
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+// Heatmap of the explored costs as PPM and PNG, with the path in white, and
+// an SVG with the path drawn over the heatmap
+pub fn export(content: &str, part: u32) -> Result<Vec<Export>, ParseError> {
+    let tile = read_risks(content)?;
+    let risks = if part == 2 { full_map(&tile) } else { tile };
+
+    let cave = Cave::new(&risks);
+    let exploration = search::astar_explored(&cave, (0, 0), |&pos| pos == cave.end);
+    let path = exploration.path.as_ref().expect("the cells of a grid are all connected");
+
+    let heatmap = cost_heatmap(&risks, &exploration);
+
+    let mut with_path = heatmap.clone();
+
+    for &(i, j) in &path.nodes {
+        with_path.set(j, i, [255, 255, 255]);
+    }
+
+    Ok(vec![
+        Export::new("heatmap.ppm", with_path.to_ppm()),
+        Export::new("heatmap.png", with_path.to_png()),
+        Export::new("path.svg", path_svg(&heatmap, path)),
+    ])
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let risks = read_risks(content)?;
+
+    log!(logger, "nrows: {}, ncols: {}", risks.nrows(), risks.ncols());
+
+    let path = lowest_risk_path(&risks, logger);
+
+    if logger.enabled() {
+        print_path(&risks, &path, logger);
+    }
+
+    Ok(Answer::from(path.cost))
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let tile = read_risks(content)?;
+
+    log!(logger, "nrows: {}, ncols: {}", tile.nrows(), tile.ncols());
+
+    let path = lowest_risk_path(&full_map(&tile), logger);
+
+    log!(logger, "min cost: {}", path.cost);
+
+    Ok(Answer::from(path.cost))
+}
+
+pub struct Day15v4;
+
+impl Solution for Day15v4 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_risks(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+
+    fn export(&self, content: &str, part: u32) -> Option<Result<Vec<Export>, ParseError>> {
+        Some(export(content, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_can_go_up_and_left() {
+        // The cheap way around the wall of 9s has to come back up
+        let content = "19111\n19191\n11191\n99991\n";
+
+        assert_eq!(part1(content, &Logger::silent()), Ok(Answer::from(11)));
+    }
+
+    #[test]
+    fn zero_risk_is_rejected() {
+        let err = part2("10\n11\n", &Logger::silent()).unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 2));
+    }
+
+    #[test]
+    fn exported_path() {
+        let files = export("116\n138\n213\n", 1).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+
+        assert_eq!(names, ["heatmap.ppm", "heatmap.png", "path.svg"]);
+
+        // The path goes down and then right, drawn in white
+        let ppm = &files[0].data;
+        let pixels = &ppm[ppm.len() - 27..];
+        let white = |i: usize, j: usize| pixels[3 * (3 * i + j)..3 * (3 * i + j) + 3] == [255, 255, 255];
+
+        assert!(white(0, 0) && white(1, 0) && white(2, 0) && white(2, 1) && white(2, 2));
+        assert!(!white(0, 2));
+
+        let svg = String::from_utf8(files[2].data.clone()).unwrap();
+        assert!(svg.contains(r#"points="0.5,0.5 0.5,1.5 0.5,2.5 1.5,2.5 2.5,2.5""#));
+        assert!(svg.contains("<title>lowest total risk: 7</title>"));
+    }
+
+    #[test]
+    fn tiled_map() {
+        let tile = read_risks("8").unwrap();
+
+        assert_eq!(full_map(&tile).row(0), [8, 9, 1, 2, 3]);
+        assert_eq!(full_map(&tile).column(4).copied().collect::<Vec<u32>>(), [3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,1403 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TypeId {
+    Sum,
+    Prod,
+    Min,
+    Max,
+    Lit,
+    GreaterThan,
+    LessThan,
+    Equal,
+}
+
+impl TypeId {
+    // None past the 3 bits of the field
+    fn from_u8(value: u8) -> Option<TypeId> {
+        match value {
+            0 => Some(TypeId::Sum),
+            1 => Some(TypeId::Prod),
+            2 => Some(TypeId::Min),
+            3 => Some(TypeId::Max),
+            4 => Some(TypeId::Lit),
+            5 => Some(TypeId::GreaterThan),
+            6 => Some(TypeId::LessThan),
+            7 => Some(TypeId::Equal),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            TypeId::Sum => 0,
+            TypeId::Prod => 1,
+            TypeId::Min => 2,
+            TypeId::Max => 3,
+            TypeId::Lit => 4,
+            TypeId::GreaterThan => 5,
+            TypeId::LessThan => 6,
+            TypeId::Equal => 7,
+        }
+    }
+
+    // Name in the S-expressions
+    fn name(self) -> &'static str {
+        match self {
+            TypeId::Sum => "sum",
+            TypeId::Prod => "prod",
+            TypeId::Min => "min",
+            TypeId::Max => "max",
+            TypeId::Lit => "lit",
+            TypeId::GreaterThan => "gt",
+            TypeId::LessThan => "lt",
+            TypeId::Equal => "eq",
+        }
+    }
+
+    // Operators only, literals are plain numbers
+    fn from_name(name: &str) -> Option<TypeId> {
+        let type_id = (0..8)
+            .filter_map(TypeId::from_u8)
+            .find(|t| t.name() == name)?;
+
+        if type_id == TypeId::Lit { None } else { Some(type_id) }
+    }
+
+    fn is_comparison(self) -> bool {
+        matches!(self, TypeId::GreaterThan | TypeId::LessThan | TypeId::Equal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketData {
+    Literal(u64),
+    Operator(Vec<Packet>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub type_id: TypeId,
+
+    pub data: PacketData,
+}
+
+// How an operator tells where its sub-packets end, the length type ID
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LengthType {
+    // 0, the sub-packets total length in bits, up to 15 bits wide
+    Bits,
+
+    // 1, the number of sub-packets, up to 11 bits wide
+    Count,
+}
+
+// Bits appended one field at a time, the most significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter { bytes: vec![], len: 0 }
+    }
+
+    fn push_bit(&mut self, bit: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+
+        self.len += 1;
+    }
+
+    // The `width` low bits of `value`
+    fn write(&mut self, value: u64, width: usize) {
+        for i in (0..width).rev() {
+            self.push_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn append(&mut self, other: &BitWriter) {
+        for i in 0..other.len {
+            self.push_bit(other.bytes[i / 8] & (0x80 >> (i % 8)) != 0);
+        }
+    }
+
+    // Padded with zeros up to a whole hex digit
+    fn to_hex(&self) -> String {
+        let digits = self.len.div_ceil(4);
+
+        self.bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<String>()[..digits]
+            .to_string()
+    }
+}
+
+// Bits of a transmission read from its raw bytes, the most significant bit
+// first
+struct BitReader {
+    bytes: Vec<u8>,
+    pos: usize,
+
+    // Bits past this one can't be read, it moves back while the sub-packets
+    // of a known length are read
+    end: usize,
+}
+
+impl BitReader {
+    // None when `hex` has something else than hexadecimal digits
+    fn from_hex(hex: &str) -> Option<Self> {
+        let mut bytes = Vec::with_capacity(hex.len().div_ceil(2));
+        let mut digits = 0;
+
+        for c in hex.chars() {
+            let nibble = c.to_digit(16)? as u8;
+
+            if digits % 2 == 0 {
+                bytes.push(nibble << 4);
+            } else {
+                *bytes.last_mut().unwrap() |= nibble;
+            }
+
+            digits += 1;
+        }
+
+        Some(BitReader { bytes, pos: 0, end: 4 * digits })
+    }
+
+    fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+
+    // The next `width` bits as a number, up to 64 of them. None when the bits
+    // end first.
+    fn read(&mut self, width: usize) -> Option<u64> {
+        if width > self.remaining() {
+            return None;
+        }
+
+        let mut value = 0u64;
+        let mut left = width;
+
+        // The rest of the current byte, or the part of it the field needs
+        while left > 0 {
+            let offset = self.pos % 8;
+            let take = left.min(8 - offset);
+            let byte = self.bytes[self.pos / 8] as u64;
+
+            let chunk = (byte >> (8 - offset - take)) & ((1 << take) - 1);
+
+            value = (value << take) | chunk;
+
+            self.pos += take;
+            left -= take;
+        }
+
+        Some(value)
+    }
+}
+
+// Problem found while decoding a transmission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    // Bit where the digit, field or packet at fault starts
+    pub offset: usize,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit {}: {}", self.offset, self.reason)
+    }
+}
+
+// The smallest packet, a literal with a single group
+const MIN_PACKET_BITS: usize = 11;
+
+// Operators nested deeper than this are refused when decoding or parsing,
+// packets still clone, compare, drop and encode recursively
+const MAX_DEPTH: usize = 1000;
+
+// Top-level packets of a transmission one after the other, until only zeros
+// are left. Decoding goes on past the problems that leave the layout of the
+// packets intact, an operator without operands or a literal too wide. It
+// stops at the first one that doesn't, a transmission cut short.
+pub struct Decoder {
+    bits: BitReader,
+
+    // Bits in the transmission
+    len: usize,
+
+    // Bit after the last 1, what follows is padding
+    last_one: usize,
+
+    errors: Vec<DecodeError>,
+    done: bool,
+}
+
+impl Decoder {
+    // Nothing is decoded from a transmission with invalid digits, each of
+    // them is reported
+    pub fn new(hex: &str) -> Self {
+        let errors: Vec<DecodeError> = hex
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_ascii_hexdigit())
+            .map(|(i, _)| DecodeError { offset: 4 * i, reason: "not a hexadecimal digit" })
+            .collect();
+
+        let bits = if errors.is_empty() {
+            BitReader::from_hex(hex).expect("the digits were checked")
+        } else {
+            BitReader { bytes: vec![], pos: 0, end: 0 }
+        };
+
+        let last_one = bits.bytes
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(0, |i| 8 * i + 8 - bits.bytes[i].trailing_zeros() as usize);
+
+        let len = bits.end;
+        let done = !errors.is_empty();
+
+        Decoder { bits, len, last_one, errors, done }
+    }
+
+    // Problems found so far, in the order of the bits
+    pub fn errors(&self) -> &[DecodeError] {
+        &self.errors
+    }
+
+    fn error(&mut self, offset: usize, reason: &'static str) {
+        self.errors.push(DecodeError { offset, reason });
+    }
+
+    fn at_padding(&self) -> bool {
+        self.bits.pos >= self.last_one
+    }
+
+    fn read(&mut self, width: usize) -> Option<u64> {
+        let value = self.bits.read(width);
+
+        if value.is_none() {
+            // Stopped by the length of an operator, not by the end
+            let reason = if self.bits.pos + width <= self.len {
+                "sub-packets run past their length"
+            } else {
+                "transmission ends in the middle of a packet"
+            };
+
+            self.error(self.bits.pos, reason);
+        }
+
+        value
+    }
+
+    fn packet(&mut self) -> Option<Packet> {
+        self.packet_at(1)
+    }
+
+    // The packet `depth` levels down from the top-level one
+    fn packet_at(&mut self, depth: usize) -> Option<Packet> {
+        let start = self.bits.pos;
+
+        let version = self.read(3)? as u8;
+        let type_id = TypeId::from_u8(self.read(3)? as u8)?;
+
+        let data = if type_id == TypeId::Lit {
+            let mut value: u64 = 0;
+            let mut too_wide = false;
+
+            loop {
+                let group_start = self.bits.pos;
+                let group = self.read(5)?;
+
+                // The value keeps its low 64 bits
+                if value >> 60 != 0 && !too_wide {
+                    too_wide = true;
+                    self.error(group_start, "literal wider than 64 bits");
+                }
+
+                value = (value << 4) | (group & 0xf);
+
+                if group & 0x10 == 0 {
+                    break;
+                }
+            }
+
+            PacketData::Literal(value)
+        } else {
+            let mut subpackets: Vec<Packet> = vec![];
+
+            if depth >= MAX_DEPTH {
+                self.error(start, "packets nested too deep");
+                return None;
+            }
+
+            if self.read(1)? == 0 {
+                let size = self.read(15)? as usize;
+
+                if size > self.bits.remaining() {
+                    self.error(self.bits.pos, "transmission ends in the middle of a packet");
+                    return None;
+                }
+
+                // The sub-packets can't read past their length
+                let end = self.bits.pos + size;
+                let outer_end = std::mem::replace(&mut self.bits.end, end);
+
+                while self.bits.pos < end {
+                    subpackets.push(self.packet_at(depth + 1)?);
+                }
+
+                self.bits.end = outer_end;
+            } else {
+                let count = self.read(11)?;
+
+                for _ in 0..count {
+                    subpackets.push(self.packet_at(depth + 1)?);
+                }
+            }
+
+            let packet = Packet { version, type_id, data: PacketData::Operator(subpackets) };
+
+            if let Some(reason) = packet.own_operands_error() {
+                self.error(start, reason);
+            }
+
+            packet.data
+        };
+
+        Some(Packet { version, type_id, data })
+    }
+}
+
+impl Iterator for Decoder {
+    type Item = Packet;
+
+    fn next(&mut self) -> Option<Packet> {
+        if self.done || self.at_padding() {
+            self.done = true;
+            return None;
+        }
+
+        // Ones left that can't make a packet
+        if self.bits.remaining() < MIN_PACKET_BITS {
+            self.error(self.bits.pos, "padding after the last packet isn't zeros");
+            self.done = true;
+            return None;
+        }
+
+        let packet = self.packet();
+
+        self.done = packet.is_none();
+        packet
+    }
+}
+
+// The only packet of a transmission, followed by zeros
+pub fn decode(hex: &str) -> Result<Packet, Vec<DecodeError>> {
+    let mut decoder = Decoder::new(hex);
+    let packet = decoder.next();
+
+    if packet.is_some() && !decoder.at_padding() {
+        let offset = decoder.bits.pos;
+
+        if decoder.bits.remaining() < MIN_PACKET_BITS {
+            decoder.error(offset, "padding after the last packet isn't zeros");
+        } else {
+            decoder.error(offset, "data after the end of the packet");
+        }
+    }
+
+    match packet {
+        Some(packet) if decoder.errors.is_empty() => Ok(packet),
+        None if decoder.errors.is_empty() => Err(vec![DecodeError { offset: 0, reason: "no packet in the transmission" }]),
+        _ => Err(decoder.errors),
+    }
+}
+
+// Every packet of a transmission of concatenated packets
+pub fn decode_stream(hex: &str) -> Result<Vec<Packet>, Vec<DecodeError>> {
+    let mut decoder = Decoder::new(hex);
+    let packets: Vec<Packet> = decoder.by_ref().collect();
+
+    if decoder.errors.is_empty() { Ok(packets) } else { Err(decoder.errors) }
+}
+
+impl Packet {
+    // The first packet of `input` as long as its layout is sound, see
+    // `decode` to validate it
+    pub fn new(input: &str) -> Option<Packet> {
+        let mut decoder = Decoder::new(input);
+
+        if decoder.errors.is_empty() { decoder.packet() } else { None }
+    }
+
+    // Hex transmission of the packet, the operators tell the length of their
+    // sub-packets in bits
+    pub fn encode(&self) -> Result<String, &'static str> {
+        self.encode_with(LengthType::Bits)
+    }
+
+    // Operators whose sub-packets don't fit `length_type` use the other one,
+    // those that fit neither the 15-bit length nor the 11-bit count can't be
+    // encoded
+    pub fn encode_with(&self, length_type: LengthType) -> Result<String, &'static str> {
+        let mut bits = BitWriter::new();
+
+        self.write_bits(&mut bits, length_type)?;
+        Ok(bits.to_hex())
+    }
+
+    fn write_bits(&self, bits: &mut BitWriter, length_type: LengthType) -> Result<(), &'static str> {
+        bits.write(self.version as u64, 3);
+        bits.write(self.type_id.to_u8() as u64, 3);
+
+        match &self.data {
+            PacketData::Literal(value) => {
+                // Groups of 4 bits, as few as the value needs, all but the
+                // last one with the continuation bit set
+                let groups = ((64 - value.leading_zeros() as usize).div_ceil(4)).max(1);
+
+                for g in (0..groups).rev() {
+                    bits.write((g > 0) as u64, 1);
+                    bits.write(value >> (4 * g), 4);
+                }
+            },
+            PacketData::Operator(subpackets) => {
+                let mut sub_bits = BitWriter::new();
+
+                for p in subpackets {
+                    p.write_bits(&mut sub_bits, length_type)?;
+                }
+
+                let fit_bits = sub_bits.len < 1 << 15;
+                let fit_count = subpackets.len() < 1 << 11;
+
+                let use_bits = match length_type {
+                    LengthType::Bits if fit_bits => true,
+                    LengthType::Count if fit_count => false,
+                    _ if fit_bits || fit_count => fit_bits,
+                    _ => return Err("sub-packets too long for a 15-bit length and too many for an 11-bit count"),
+                };
+
+                if use_bits {
+                    bits.write(0, 1);
+                    bits.write(sub_bits.len as u64, 15);
+                } else {
+                    bits.write(1, 1);
+                    bits.write(subpackets.len() as u64, 11);
+                }
+
+                bits.append(&sub_bits);
+            },
+        }
+
+        Ok(())
+    }
+
+    // e.g. `(sum (prod 6 9) (min 3 7))`, with `versions` each version
+    // follows its packet, `(sum@1 (prod@3 6@2 9@0) ...)`
+    pub fn to_sexpr(&self, versions: bool) -> String {
+        let mut out = String::new();
+
+        self.write_sexpr(&mut out, versions);
+        out
+    }
+
+    // Without recursion, each operator on the stack with the index of its
+    // next sub-packet
+    fn write_sexpr(&self, out: &mut String, versions: bool) {
+        let version = |out: &mut String, packet: &Packet| {
+            if versions {
+                out.push_str(&format!("@{}", packet.version));
+            }
+        };
+
+        let mut stack = vec![(self, 0)];
+
+        while let Some((packet, next)) = stack.pop() {
+            match &packet.data {
+                PacketData::Literal(value) => {
+                    out.push_str(&value.to_string());
+                    version(out, packet);
+                },
+                PacketData::Operator(subpackets) => {
+                    if next == 0 {
+                        out.push('(');
+                        out.push_str(packet.type_id.name());
+                        version(out, packet);
+                    }
+
+                    if let Some(p) = subpackets.get(next) {
+                        out.push(' ');
+                        stack.push((packet, next + 1));
+                        stack.push((p, 0));
+                    } else {
+                        out.push(')');
+                    }
+                },
+            }
+        }
+    }
+
+    // One packet per line, the sub-packets indented under their operator
+    pub fn to_tree(&self) -> String {
+        let mut out = String::new();
+
+        self.write_tree(&mut out, 0);
+        out
+    }
+
+    fn write_tree(&self, out: &mut String, depth: usize) {
+        let label = match &self.data {
+            PacketData::Literal(value) => value.to_string(),
+            PacketData::Operator(_) => self.type_id.name().to_string(),
+        };
+
+        out.push_str(&format!("{}{} v{}\n", "  ".repeat(depth), label, self.version));
+
+        if let PacketData::Operator(subpackets) = &self.data {
+            for p in subpackets {
+                p.write_tree(out, depth + 1);
+            }
+        }
+    }
+
+    // Why `calc` can't evaluate the packet, an operator without the operands
+    // it needs
+    pub fn operands_error(&self) -> Option<&'static str> {
+        match &self.data {
+            PacketData::Literal(_) => None,
+            PacketData::Operator(subpackets) => self
+                .own_operands_error()
+                .or_else(|| subpackets.iter().find_map(|p| p.operands_error())),
+        }
+    }
+
+    // Same as `operands_error` for the packet itself, not its sub-packets
+    fn own_operands_error(&self) -> Option<&'static str> {
+        let subpackets = match &self.data {
+            PacketData::Literal(_) => return None,
+            PacketData::Operator(subpackets) => subpackets,
+        };
+
+        if subpackets.is_empty() {
+            Some("operator without operands")
+        } else if self.type_id.is_comparison() && subpackets.len() != 2 {
+            Some("comparison without exactly two operands")
+        } else {
+            None
+        }
+    }
+
+    // The first operator from the left whose operands are all literals
+    // replaced by its value, None once the packet is a literal or when the
+    // operator can't be evaluated
+    pub fn reduce_step(&self) -> Option<Packet> {
+        let subpackets = match &self.data {
+            PacketData::Literal(_) => return None,
+            PacketData::Operator(subpackets) => subpackets,
+        };
+
+        for (k, p) in subpackets.iter().enumerate() {
+            if let Some(reduced) = p.reduce_step() {
+                let mut subpackets = subpackets.clone();
+                subpackets[k] = reduced;
+
+                return Some(Packet {
+                    version: self.version,
+                    type_id: self.type_id,
+                    data: PacketData::Operator(subpackets),
+                });
+            }
+        }
+
+        Some(Packet {
+            version: self.version,
+            type_id: TypeId::Lit,
+            data: PacketData::Literal(self.calc().ok()?),
+        })
+    }
+
+    pub fn sum_versions(&self) -> usize {
+        let mut result = 0;
+        let mut stack = vec![self];
+
+        while let Some(p) = stack.pop() {
+            result += p.version as usize;
+
+            if let PacketData::Operator(sub) = &p.data {
+                stack.extend(sub.iter());
+            }
+        }
+
+        result
+    }
+
+    // The value of the expression, or why it has none: an operator without
+    // the operands it needs or a value past 64 bits
+    pub fn calc(&self) -> Result<u64, &'static str> {
+        // Operators with the index of their next sub-packet, the values of
+        // the sub-packets already evaluated on top of `values`
+        let mut stack = vec![(self, 0)];
+        let mut values: Vec<u64> = vec![];
+
+        while let Some((packet, next)) = stack.pop() {
+            match &packet.data {
+                PacketData::Literal(value) => values.push(*value),
+                PacketData::Operator(subpackets) => {
+                    if let Some(p) = subpackets.get(next) {
+                        stack.push((packet, next + 1));
+                        stack.push((p, 0));
+                    } else {
+                        let start = values.len() - subpackets.len();
+                        let value = packet.apply(&values[start..])?;
+
+                        values.truncate(start);
+                        values.push(value);
+                    }
+                },
+            }
+        }
+
+        Ok(values[0])
+    }
+
+    // The operator applied to the values of its sub-packets
+    fn apply(&self, values: &[u64]) -> Result<u64, &'static str> {
+        if let Some(reason) = self.own_operands_error() {
+            return Err(reason);
+        }
+
+        let overflow = "value overflows 64 bits";
+
+        match self.type_id {
+            TypeId::Sum => values.iter().try_fold(0u64, |total, &v| total.checked_add(v)).ok_or(overflow),
+            TypeId::Prod => values.iter().try_fold(1u64, |total, &v| total.checked_mul(v)).ok_or(overflow),
+            TypeId::Min => Ok(*values.iter().min().unwrap()),
+            TypeId::Max => Ok(*values.iter().max().unwrap()),
+            TypeId::Lit => Err("literal with sub-packets"),
+            TypeId::GreaterThan => Ok((values[0] > values[1]) as u64),
+            TypeId::LessThan => Ok((values[0] < values[1]) as u64),
+            TypeId::Equal => Ok((values[0] == values[1]) as u64),
+        }
+    }
+}
+
+// Atoms and parentheses of an S-expression, with their byte offset
+fn sexpr_tokens(s: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        if c == '(' || c == ')' || c.is_whitespace() {
+            if let Some(st) = start.take() {
+                tokens.push((st, &s[st..i]));
+            }
+
+            if !c.is_whitespace() {
+                tokens.push((i, &s[i..i + 1]));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+
+    if let Some(st) = start {
+        tokens.push((st, &s[st..]));
+    }
+
+    tokens
+}
+
+fn sexpr_error((offset, text): (usize, &str), reason: &str) -> ParseError {
+    ParseError::new(1, offset + 1, text, reason)
+}
+
+// `name@version`, the version defaults to 0
+fn annotated(token: (usize, &str)) -> Result<(&str, u8), ParseError> {
+    match token.1.split_once('@') {
+        None => Ok((token.1, 0)),
+        Some((name, version)) => match version.parse::<u8>() {
+            Ok(v) if v < 8 => Ok((name, v)),
+            _ => Err(sexpr_error(token, "versions go from 0 to 7")),
+        },
+    }
+}
+
+// The expression at token `i`, without recursion: the operators still
+// open are on a stack with their `(` token and their operands so far
+fn parse_sexpr(s: &str, tokens: &[(usize, &str)], i: &mut usize) -> Result<Packet, ParseError> {
+    let end = (s.len(), "");
+    let mut open: Vec<((usize, &str), Packet)> = vec![];
+
+    loop {
+        let token = match tokens.get(*i) {
+            Some(&token) => token,
+            None if open.is_empty() => return Err(sexpr_error(end, "expression expected")),
+            None => return Err(sexpr_error(end, "missing ')'")),
+        };
+
+        *i += 1;
+
+        let packet = match token.1 {
+            ")" => {
+                let (start, packet) = open.pop().ok_or_else(|| sexpr_error(token, "unexpected ')'"))?;
+
+                if let Some(reason) = packet.own_operands_error() {
+                    return Err(sexpr_error(start, reason));
+                }
+
+                packet
+            },
+            "(" if open.len() + 1 >= MAX_DEPTH => return Err(sexpr_error(token, "expression nested too deep")),
+            "(" => {
+                let &head = tokens.get(*i).ok_or_else(|| sexpr_error(end, "missing ')'"))?;
+                *i += 1;
+
+                let (name, version) = annotated(head)?;
+                let type_id = TypeId::from_name(name).ok_or_else(|| sexpr_error(head, "unknown operator"))?;
+
+                open.push((token, Packet { version, type_id, data: PacketData::Operator(vec![]) }));
+                continue;
+            },
+            _ => {
+                let (number, version) = annotated(token)?;
+                let value = number.parse::<u64>().map_err(|_| sexpr_error(token, "not a number"))?;
+
+                Packet { version, type_id: TypeId::Lit, data: PacketData::Literal(value) }
+            },
+        };
+
+        match open.last_mut() {
+            Some((_, Packet { data: PacketData::Operator(subpackets), .. })) => subpackets.push(packet),
+            _ => return Ok(packet),
+        }
+    }
+}
+
+// The S-expressions of `to_sexpr`, with or without the versions
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = sexpr_tokens(s);
+        let mut i = 0;
+
+        let packet = parse_sexpr(s, &tokens, &mut i)?;
+
+        match tokens.get(i) {
+            Some(&token) => Err(sexpr_error(token, "expression already complete")),
+            None => Ok(packet),
+        }
+    }
+}
+
+// The packet, its tree and its evaluation one operator at a time
+fn show(packet: &Packet, output: &mut dyn Write) -> io::Result<()> {
+    writeln!(output, "{}", packet.to_sexpr(true))?;
+    write!(output, "{}", packet.to_tree())?;
+
+    if let Some(reason) = packet.operands_error() {
+        return writeln!(output, "can't evaluate: {}", reason);
+    }
+
+    if let Err(reason) = packet.calc() {
+        return writeln!(output, "error: {}", reason);
+    }
+
+    let mut current = packet.clone();
+
+    while let Some(next) = current.reduce_step() {
+        writeln!(output, "= {}", next.to_sexpr(false))?;
+        current = next;
+    }
+
+    Ok(())
+}
+
+// Interactive mode: hex transmissions are decoded and evaluated, the
+// S-expressions compiled to hex and evaluated
+pub fn repl(input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    writeln!(output, "hex transmission or S-expression, e.g. (sum (prod 6 9) (min 3 7)), 'quit' to leave")?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            return writeln!(output);
+        }
+
+        let packet = match line.trim() {
+            "" => continue,
+            "quit" | "exit" => return Ok(()),
+            text if text.starts_with('(') => match text.parse::<Packet>() {
+                Ok(packet) => {
+                    match packet.encode() {
+                        Ok(hex) => writeln!(output, "{}", hex)?,
+                        Err(reason) => writeln!(output, "error: {}", reason)?,
+                    }
+
+                    packet
+                },
+                Err(e) => {
+                    writeln!(output, "error: column {}: {}", e.column, e.reason)?;
+                    continue;
+                },
+            },
+            text => {
+                // Everything the transmission holds, packets and problems
+                let mut decoder = Decoder::new(text);
+                let packets: Vec<Packet> = decoder.by_ref().collect();
+
+                for e in decoder.errors() {
+                    writeln!(output, "error: {}", e)?;
+                }
+
+                for packet in &packets {
+                    show(packet, output)?;
+                }
+
+                continue;
+            },
+        };
+
+        show(&packet, output)?;
+    }
+}
+
+// The example file keeps several transmissions commented out with '#'
+fn read_transmission(content: &str) -> Result<Packet, ParseError> {
+    let line = parse::lines(content)
+        .find(|line| {
+            let r = line.text.trim();
+
+            r.len() > 0 && !r.starts_with("#")
+        })
+        .ok_or_else(|| ParseError::end_of_input(content, "no transmission found"))?;
+
+    let hex = line.text.trim();
+
+    decode(hex).map_err(|errors| {
+        // The first problem, at the digit holding its first bit
+        let e = &errors[0];
+
+        let (i, len) = hex
+            .char_indices()
+            .nth(e.offset / 4)
+            .map_or((hex.len(), 0), |(i, c)| (i, c.len_utf8()));
+
+        line.error_at(line.column_of(hex) - 1 + i, len, &e.to_string())
+    })
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let p = read_transmission(content)?;
+
+    log!(logger, "{:?}", p);
+
+    log!(logger, "versions sum: {}", p.sum_versions());
+
+    Ok(Answer::from(p.sum_versions()))
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let p = read_transmission(content)?;
+
+    log!(logger, "{:?}", p);
+
+    let value = p.calc().map_err(|reason| ParseError::end_of_input(content, reason))?;
+
+    log!(logger, "calc: {}", value);
+    log!(logger, "versions sum: {}", p.sum_versions());
+
+    Ok(Answer::from(value))
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_transmission(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+
+    fn repl(&self, input: &mut dyn BufRead, output: &mut dyn Write) -> Option<io::Result<()>> {
+        Some(repl(input, output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_value() {
+        let p = Packet::new("D2FE28").unwrap();
+
+        assert_eq!(p.version, 6);
+        assert!(matches!(p.data, PacketData::Literal(2021)));
+    }
+
+    #[test]
+    fn operator_with_sub_packets_length() {
+        let p = Packet::new("38006F45291200").unwrap();
+
+        match p.data {
+            PacketData::Operator(sub) => {
+                assert_eq!(sub.len(), 2);
+                assert_eq!(sub[0].calc(), Ok(10));
+                assert_eq!(sub[1].calc(), Ok(20));
+            },
+            _ => panic!("expecting an operator"),
+        }
+    }
+
+    #[test]
+    fn operator_with_sub_packets_count() {
+        let p = Packet::new("EE00D40C823060").unwrap();
+
+        match p.data {
+            PacketData::Operator(sub) => {
+                let values: Vec<u64> = sub.iter().map(|p| p.calc().unwrap()).collect();
+                assert_eq!(values, vec![1, 2, 3]);
+            },
+            _ => panic!("expecting an operator"),
+        }
+    }
+
+    // Every case from inputs/day16-example.txt, not only the uncommented one
+    #[test]
+    fn versions_sum() {
+        let cases = [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ];
+
+        for (hex, expected) in cases {
+            assert_eq!(Packet::new(hex).unwrap().sum_versions(), expected, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn calc() {
+        let cases = [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ];
+
+        for (hex, expected) in cases {
+            assert_eq!(Packet::new(hex).unwrap().calc(), Ok(expected), "{}", hex);
+        }
+
+        // Packets built by hand can lack operands
+        let empty = operator(TypeId::Max, vec![]);
+        let half = operator(TypeId::LessThan, vec![literal(0, 1)]);
+
+        assert_eq!(empty.calc(), Err("operator without operands"));
+        assert_eq!(operator(TypeId::Sum, vec![literal(0, 2), half.clone()]).calc(), Err("comparison without exactly two operands"));
+        assert_eq!(empty.reduce_step(), None);
+        assert_eq!(half.reduce_step(), None);
+    }
+
+    #[test]
+    fn malformed_transmissions() {
+        let logger = Logger::silent();
+
+        let err = part1("# comment\nD2FG28\n", &logger).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 4, "G", "bit 12: not a hexadecimal digit"));
+
+        // The literal of D2FE28 is cut after its first group, in the third
+        // digit
+        let err = part1("D2F", &logger).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+        assert_eq!(err.reason, "bit 11: transmission ends in the middle of a packet");
+
+        // A comparison with a single operand can't be evaluated
+        let hex = operator(TypeId::GreaterThan, vec![literal(0, 1)]).encode().unwrap();
+        let err = part2(&hex, &logger).unwrap_err();
+        assert_eq!((err.column, err.reason.as_str()), (1, "bit 0: comparison without exactly two operands"));
+
+        assert!(part2("# only comments\n", &logger).is_err());
+    }
+
+    fn literal(version: u8, value: u64) -> Packet {
+        Packet { version, type_id: TypeId::Lit, data: PacketData::Literal(value) }
+    }
+
+    fn operator(type_id: TypeId, subpackets: Vec<Packet>) -> Packet {
+        Packet { version: 0, type_id, data: PacketData::Operator(subpackets) }
+    }
+
+    // Packets back to back, without padding between them
+    fn stream(packets: &[Packet], length_type: LengthType) -> String {
+        let mut bits = BitWriter::new();
+
+        for p in packets {
+            p.write_bits(&mut bits, length_type).unwrap();
+        }
+
+        bits.to_hex()
+    }
+
+    fn error(offset: usize, reason: &'static str) -> DecodeError {
+        DecodeError { offset, reason }
+    }
+
+    #[test]
+    fn decoding_streams() {
+        let packets = [
+            literal(1, 7),
+            operator(TypeId::Max, vec![literal(2, 1), literal(3, 5)]),
+            literal(4, 0),
+        ];
+
+        for length_type in [LengthType::Bits, LengthType::Count] {
+            let hex = stream(&packets, length_type);
+
+            assert_eq!(decode_stream(&hex), Ok(packets.to_vec()));
+            assert_eq!(decode(&hex), Err(vec![error(11, "data after the end of the packet")]));
+        }
+
+        // An all zero packet is only padding at the end
+        let zero = operator(TypeId::Sum, vec![]);
+        let hex = stream(&[zero.clone(), literal(0, 3)], LengthType::Bits);
+
+        assert_eq!(decode_stream(&hex), Err(vec![error(0, "operator without operands")]));
+        assert_eq!(Packet::new(&hex), Some(zero));
+        assert_eq!(decode_stream("0000"), Ok(vec![]));
+        assert_eq!(decode("00"), Err(vec![error(0, "no packet in the transmission")]));
+    }
+
+    #[test]
+    fn decoding_errors() {
+        assert_eq!(decode("D2FE28"), Ok(literal(6, 2021)));
+
+        assert_eq!(
+            decode("1G3Z"),
+            Err(vec![error(4, "not a hexadecimal digit"), error(12, "not a hexadecimal digit")])
+        );
+
+        assert_eq!(decode("D2F"), Err(vec![error(11, "transmission ends in the middle of a packet")]));
+        assert_eq!(decode("D2FE29"), Err(vec![error(21, "padding after the last packet isn't zeros")]));
+
+        // The literal overruns the 11 bits its operator claims for it
+        let mut bits = BitWriter::new();
+        bits.write(0, 7);
+        bits.write(11, 15);
+        bits.write(0b100_100, 6);
+        bits.write(0b10001_00001, 10);
+
+        assert_eq!(decode(&bits.to_hex()), Err(vec![error(33, "sub-packets run past their length")]));
+    }
+
+    #[test]
+    fn decoding_goes_on_after_operand_errors() {
+        let packet = operator(TypeId::Sum, vec![
+            literal(0, 1),
+            operator(TypeId::LessThan, vec![literal(0, 1), literal(0, 2), literal(0, 3)]),
+            operator(TypeId::Min, vec![]),
+        ]);
+
+        assert_eq!(
+            decode(&packet.encode().unwrap()),
+            Err(vec![
+                error(33, "comparison without exactly two operands"),
+                error(88, "operator without operands"),
+            ])
+        );
+
+        // 17 groups, the last one doesn't fit in 64 bits
+        let mut bits = BitWriter::new();
+        bits.write(0b100, 6);
+
+        for _ in 0..16 {
+            bits.write(0b11111, 5);
+        }
+
+        bits.write(0b00001, 5);
+
+        let mut decoder = Decoder::new(&bits.to_hex());
+
+        assert_eq!(decoder.next(), Some(literal(0, u64::MAX << 4 | 1)));
+        assert_eq!(decoder.errors(), [error(86, "literal wider than 64 bits")]);
+    }
+
+    #[test]
+    fn bit_fields() {
+        let mut bits = BitReader::from_hex("D2FE28").unwrap();
+
+        assert_eq!(bits.read(3), Some(6));
+        assert_eq!(bits.read(3), Some(4));
+        assert_eq!(bits.read(15), Some(0b101111111000101));
+        assert_eq!(bits.read(4), None);
+        assert_eq!(bits.read(3), Some(0));
+
+        let mut bits = BitReader::from_hex("FFFFFFFFFFFFFFFFF").unwrap();
+        assert_eq!(bits.read(2), Some(3));
+        assert_eq!(bits.read(64), Some(u64::MAX));
+
+        assert!(BitReader::from_hex("12G").is_none());
+    }
+
+    // `depth` sums of a single operand around a 5, each one with its count of
+    // sub-packets
+    fn nested_sums(depth: usize) -> String {
+        let mut bits = BitWriter::new();
+
+        for _ in 0..depth {
+            bits.write(0, 6);
+            bits.write(1, 1);
+            bits.write(1, 11);
+        }
+
+        bits.write(4, 6);
+        bits.write(5, 5);
+        bits.to_hex()
+    }
+
+    #[test]
+    fn deeply_nested_packets() {
+        // 1.35 MB of hex, refused without exhausting the stack
+        let hex = nested_sums(300_000);
+        let errors = decode(&hex).unwrap_err();
+
+        assert_eq!(errors, [error(18 * (MAX_DEPTH - 1), "packets nested too deep")]);
+        assert!(part1(&hex, &Logger::silent()).is_err());
+
+        let sexpr = format!("{}1{}", "(sum ".repeat(300_000), ")".repeat(300_000));
+        let e = sexpr.parse::<Packet>().unwrap_err();
+        assert_eq!((e.column, e.reason.as_str()), (5 * (MAX_DEPTH - 1) + 1, "expression nested too deep"));
+
+        // As deep as they go
+        let packet = decode(&nested_sums(MAX_DEPTH - 1)).unwrap();
+        let sexpr = packet.to_sexpr(true);
+
+        assert_eq!(packet.calc(), Ok(5));
+        assert_eq!(packet.sum_versions(), 0);
+        assert_eq!(sexpr.parse::<Packet>().as_ref(), Ok(&packet));
+        assert_eq!(decode(&packet.encode_with(LengthType::Count).unwrap()).as_ref(), Ok(&packet));
+    }
+
+    #[test]
+    fn large_transmission() {
+        // Half a million literals in 500 sums, megabytes of hex
+        let sum = |subpackets| operator(TypeId::Sum, subpackets);
+        let packet = sum((0..500).map(|i| sum((0..1000).map(|j| literal(1, i + j)).collect())).collect());
+        let hex = packet.encode().unwrap();
+
+        assert!(hex.len() > 1_000_000);
+
+        let decoded = Packet::new(&hex).unwrap();
+        assert_eq!(decoded.calc(), Ok(374_500_000));
+        assert_eq!(decoded.sum_versions(), 500_000);
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(Packet::new("D2FE28").unwrap().encode().unwrap(), "D2FE28");
+        assert_eq!(Packet::new("38006F45291200").unwrap().encode().unwrap(), "38006F4529120");
+        assert_eq!(Packet::new("EE00D40C823060").unwrap().encode_with(LengthType::Count).unwrap(), "EE00D40C82306");
+
+        // A zero still takes a group
+        let zero = literal(0, 0);
+        assert_eq!(zero.encode().unwrap(), "100");
+
+        // Too many sub-packets for the count, short enough for the length
+        let sum = |subpackets| operator(TypeId::Sum, subpackets);
+        let many = sum(vec![zero.clone(); 1 << 11]);
+        let hex = many.encode_with(LengthType::Count).unwrap();
+        assert_eq!(Packet::new(&hex), Some(many));
+
+        // Too long for the length, few enough for the count
+        let long = sum(vec![literal(0, u64::MAX); 400]);
+        let hex = long.encode().unwrap();
+        assert_eq!(Packet::new(&hex), Some(long));
+
+        // Neither
+        assert!(sum(vec![literal(0, u64::MAX); 1 << 11]).encode().is_err());
+        assert!(sum(vec![literal(0, u64::MAX); 1 << 11]).encode_with(LengthType::Count).is_err());
+    }
+
+    #[test]
+    fn sexpr() {
+        let p = Packet::new("9C0141080250320F1802104A08").unwrap();
+
+        assert_eq!(p.to_sexpr(false), "(eq (sum 1 3) (prod 2 2))");
+        assert_eq!(p.to_sexpr(true), "(eq@4 (sum@2 1@2 3@4) (prod@6 2@0 2@2))");
+        assert_eq!(p.to_tree(), "eq v4\n  sum v2\n    1 v2\n    3 v4\n  prod v6\n    2 v0\n    2 v2\n");
+
+        assert_eq!(p.to_sexpr(true).parse::<Packet>(), Ok(p.clone()));
+        assert_eq!(" ( eq (sum 1 3)(prod 2 2) ) ".parse::<Packet>().unwrap().to_sexpr(false), p.to_sexpr(false));
+        assert_eq!("42@3".parse::<Packet>().unwrap().encode().unwrap(), "724A");
+    }
+
+    #[test]
+    fn malformed_sexprs() {
+        let error = |s: &str| {
+            let e = s.parse::<Packet>().unwrap_err();
+            (e.column, e.reason)
+        };
+
+        assert_eq!(error("(sum 1 2"), (9, String::from("missing ')'")));
+        assert_eq!(error("(add 1 2)"), (2, String::from("unknown operator")));
+        assert_eq!(error("(sum 1 x)"), (8, String::from("not a number")));
+        assert_eq!(error("(sum@8 1)"), (2, String::from("versions go from 0 to 7")));
+        assert_eq!(error("(sum 1) 2"), (9, String::from("expression already complete")));
+        assert_eq!(error("(max (min) 1)"), (6, String::from("operator without operands")));
+        assert_eq!(error("(lt 1 2 3)"), (1, String::from("comparison without exactly two operands")));
+        assert_eq!(error(")"), (1, String::from("unexpected ')'")));
+    }
+
+    #[test]
+    fn step_by_step() {
+        let mut p: Packet = "(sum (prod 6 9) (min 3 7))".parse().unwrap();
+        let mut steps = vec![];
+
+        while let Some(next) = p.reduce_step() {
+            steps.push(next.to_sexpr(false));
+            p = next;
+        }
+
+        assert_eq!(steps, ["(sum 54 (min 3 7))", "(sum 54 3)", "57"]);
+    }
+
+    #[test]
+    fn interactive_session() {
+        let mut input = "D2FE28\n\n(gt 1)\n(max 2 5)\nquit\n(sum 1)\n".as_bytes();
+        let mut output = vec![];
+
+        repl(&mut input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().skip(1).collect();
+
+        assert_eq!(lines, [
+            "> 2021@6",
+            "2021 v6",
+            "> > error: column 1: comparison without exactly two operands",
+            "> 0C005841085",
+            "(max@0 2@0 5@0)",
+            "max v0",
+            "  2 v0",
+            "  5 v0",
+            "= 5",
+            "> ",
+        ]);
+    }
+
+    #[test]
+    fn overflow() {
+        let packet: Packet = "(prod 18446744073709551615 2)".parse().unwrap();
+        assert_eq!(packet.calc(), Err("value overflows 64 bits"));
+        assert!(packet.reduce_step().is_none());
+
+        let packet: Packet = "(min (sum 18446744073709551615 1) 3)".parse().unwrap();
+        assert_eq!(packet.calc(), Err("value overflows 64 bits"));
+        assert!(part2(&packet.encode().unwrap(), &Logger::silent()).is_err());
+
+        let mut input = "(prod 18446744073709551615 2)
+(max 18446744073709551615 2)
+".as_bytes();
+        let mut output = vec![];
+
+        repl(&mut input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().filter(|line| line.contains('=') || line.contains("error")).collect();
+
+        assert_eq!(lines, ["error: value overflows 64 bits", "= 18446744073709551615"]);
+    }
+
+    // xorshift64, enough to make up packets
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    fn random_packet(rng: &mut Rng, depth: u32) -> Packet {
+        let version = rng.below(8) as u8;
+        let type_id = TypeId::from_u8(rng.below(8) as u8).unwrap();
+
+        let data = if depth == 0 || type_id == TypeId::Lit {
+            // Values of every width, up to the full 64 bits
+            let bits = rng.below(65);
+            PacketData::Literal(if bits == 0 { 0 } else { rng.next() >> (64 - bits) })
+        } else {
+            let count = rng.below(5);
+            PacketData::Operator((0..count).map(|_| random_packet(rng, depth - 1)).collect())
+        };
+
+        // Literals have their own type whatever was drawn
+        let type_id = match data {
+            PacketData::Literal(_) => TypeId::Lit,
+            PacketData::Operator(_) if type_id == TypeId::Lit => TypeId::Sum,
+            _ => type_id,
+        };
+
+        Packet { version, type_id, data }
+    }
+
+    #[test]
+    fn encoding_round_trip() {
+        let mut rng = Rng(0x2021_1216);
+
+        for _ in 0..500 {
+            let packet = random_packet(&mut rng, 4);
+
+            for length_type in [LengthType::Bits, LengthType::Count] {
+                let hex = packet.encode_with(length_type).unwrap();
+                assert_eq!(Packet::new(&hex).as_ref(), Some(&packet), "{}", hex);
+            }
+
+            // Only packets that can be evaluated are written as S-expressions
+            if packet.operands_error().is_none() {
+                assert_eq!(packet.to_sexpr(true).parse().as_ref(), Ok(&packet));
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use std::fs;
 use std::collections::HashSet;
 
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
 #[derive(Debug)]
 struct Area {
     min_x: i32,
@@ -22,6 +25,7 @@ impl Area {
         return 0;
     }
 
+    #[allow(dead_code)]
     fn y_comp(&self, y: i32) -> i32 {
         if y < self.min_y {
             return -1;
@@ -43,47 +47,72 @@ fn abs(v: i32) -> i32 {
     return -v;
 }
 
-fn extract_range(data: &str) -> (i32, i32) {
-    let splited: Vec<i32> = data
-        .to_string()
-        .drain(2..)
-        .collect::<String>()
+// Parses "x=A..B" with `axis` as the expected name
+fn extract_range(line: &parse::Line, data: &str, axis: &str) -> Result<(i32, i32), ParseError> {
+    let range = data
+        .strip_prefix(axis)
+        .and_then(|r| r.strip_prefix("="))
+        .ok_or_else(|| line.error(data, &format!("expecting the {} range", axis)))?;
+
+    let splited: Vec<&str> = range
         .split("..")
-        .map(|n| {
-            i32::from_str_radix(n, 10)
-                .unwrap()
-        })
         .collect();
 
-    (splited[0], splited[1])
+    if splited.len() != 2 {
+        return Err(line.error(range, "expecting a range as A..B"));
+    }
+
+    let min = line.parse::<i32>(splited[0])?;
+    let max = line.parse::<i32>(splited[1])?;
+
+    if min > max {
+        return Err(line.error(range, "range start is after its end"));
+    }
+
+    Ok((min, max))
 }
 
-fn part1(mut content: String) {
-    let area = {
-        let area_str = content
-            .drain(13..content.len() - 1)
-            .collect::<String>();
+fn read_area(content: &str) -> Result<Area, ParseError> {
+    let line = parse::first_line(content)?;
 
-        // println!("area: {}", area_str);
+    let area_str = line.text
+        .strip_prefix("target area: ")
+        .ok_or_else(|| line.error(line.text, "expecting 'target area: '"))?;
 
-        let splited: Vec<&str> = area_str
-            .split(", ")
-            .collect();
+    let splited: Vec<&str> = area_str
+        .split(", ")
+        .collect();
 
-        let xrange = extract_range(splited[0]);
-        let yrange = extract_range(splited[1]);
+    if splited.len() != 2 {
+        return Err(line.error(area_str, "expecting the x and y ranges"));
+    }
 
-        Area {
-            min_x: xrange.0,
-            max_x: xrange.1,
-            min_y: yrange.0,
-            max_y: yrange.1,
-        }
-    };
+    let xrange = extract_range(&line, splited[0], "x")?;
+    let yrange = extract_range(&line, splited[1], "y")?;
 
-    println!("area: {:?}", area);
+    // The launch below only looks for targets ahead of and under the probe
+    if xrange.1 < 0 {
+        return Err(line.error(splited[0], "the target must not be behind the probe"));
+    }
 
-    assert!(area.max_x >= 0);
+    if yrange.1 >= 0 {
+        return Err(line.error(splited[1], "the target must be below the probe"));
+    }
+
+    Ok(Area {
+        min_x: xrange.0,
+        max_x: xrange.1,
+        min_y: yrange.0,
+        max_y: yrange.1,
+    })
+}
+
+// Returns the highest reachable y position and the number of initial
+// velocities that land in the target area
+fn launch(content: &str, logger: &Logger) -> Result<(i32, usize), ParseError> {
+    let area = read_area(content)?;
+
+    log!(logger, "area: {:?}", area);
 
     let mut velocities: HashSet<(i32, i32)> = HashSet::new();
 
@@ -190,16 +219,36 @@ fn part1(mut content: String) {
         }
     }
 
-    println!("result: {:?}, {}", result, max_y);
-    println!("count: {}", velocities.len());
+    log!(logger, "result: {:?}, {}", result, max_y);
+    log!(logger, "count: {}", velocities.len());
+
+    Ok((max_y, velocities.len()))
 }
 
-fn main() {
-    // let filename = "./inputs/day17-example.txt";
-    let filename = "./inputs/day17.txt";
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (max_y, _) = launch(content, logger)?;
 
-    let content = fs::read_to_string(filename)
-        .expect("Could not read file.");
+    Ok(Answer::from(max_y))
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (_, count) = launch(content, logger)?;
+
+    Ok(Answer::from(count))
+}
 
-    part1(content);
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_area(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
 }
@@ -1,4 +1,5 @@
-use std::fs;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
 #[derive(PartialEq, Eq, Debug)]
 enum TokenType {
@@ -368,6 +369,7 @@ impl Snailfish {
         return splitted;
     }
 
+    #[allow(dead_code)]
     fn bracket_count_until(&self, pos: usize) -> usize {
         let mut i = 0;
 
@@ -531,24 +533,74 @@ impl Snailfish {
     }
 }
 
-fn part1(content: String) {
-    let mut snails = content
-        .lines()
+// Checks a pair as "[a,b]", where a and b are numbers or pairs, from `pos` and
+// returns the position right after it. Snailfish::parse expects well formed
+// tokens.
+fn check_pair(line: &parse::Line, pos: usize) -> Result<usize, ParseError> {
+    let bytes = line.text.as_bytes();
+
+    let expect = |pos: usize, c: u8| -> Result<usize, ParseError> {
+        if bytes.get(pos) == Some(&c) {
+            Ok(pos + 1)
+        } else {
+            Err(line.error_at(pos, 1, &format!("expecting '{}'", c as char)))
+        }
+    };
+
+    let element = |pos: usize| -> Result<usize, ParseError> {
+        let digits = bytes[pos..].iter().take_while(|c| c.is_ascii_digit()).count();
+
+        if digits > 0 {
+            line.parse::<u32>(&line.text[pos..pos + digits])?;
+            Ok(pos + digits)
+        } else if bytes.get(pos) == Some(&b'[') {
+            check_pair(line, pos)
+        } else {
+            Err(line.error_at(pos, 1, "expecting a number or a pair"))
+        }
+    };
+
+    let pos = expect(pos, b'[')?;
+    let pos = element(pos)?;
+    let pos = expect(pos, b',')?;
+    let pos = element(pos)?;
+
+    expect(pos, b']')
+}
+
+fn read_snails(content: &str) -> Result<Vec<Snailfish>, ParseError> {
+    let snails = parse::lines(content)
         .map(|line| {
-            let tokens = read_tokens(line);
+            let end = check_pair(&line, 0)?;
+
+            if end != line.text.len() {
+                return Err(line.error(&line.text[end..], "unexpected text after the number"));
+            }
+
+            let tokens = read_tokens(line.text);
 
             let mut snail = Snailfish::new();
 
             snail.parse(&tokens, 0);
 
-            snail
+            Ok(snail)
         })
-        .collect::<Vec<Snailfish>>();
+        .collect::<Result<Vec<Snailfish>, ParseError>>()?;
+
+    if snails.is_empty() {
+        return Err(ParseError::end_of_input(content, "no snailfish numbers"));
+    }
+
+    Ok(snails)
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
+    let mut snails = read_snails(content)?;
 
     let mut result = snails.remove(0);
 
     while snails.len() > 0 {
-        let mut n = snails.remove(0);
+        let n = snails.remove(0);
 
         result.add(n);
 
@@ -556,24 +608,11 @@ fn part1(content: String) {
         // println!("######");
     }
 
-    result.print_inline(0, false);
-
-    println!("mag: {}", result.magnetude(0));
+    Ok(Answer::from(result.magnetude(0)))
 }
 
-fn part2(content: String) {
-    let mut snails = content
-        .lines()
-        .map(|line| {
-            let tokens = read_tokens(line);
-
-            let mut snail = Snailfish::new();
-
-            snail.parse(&tokens, 0);
-
-            snail
-        })
-        .collect::<Vec<Snailfish>>();
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
+    let snails = read_snails(content)?;
 
     let mut greatest_magnetude = 0;
 
@@ -605,13 +644,131 @@ fn part2(content: String) {
         }
     }
 
-    println!("mag: {}", greatest_magnetude);
+    Ok(Answer::from(greatest_magnetude))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_snails(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
+
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
 }
 
-fn main() {
-    let filename = "inputs/day18.txt";
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let content = fs::read_to_string(filename).expect("Could not read file.");
+    fn snail(line: &str) -> Snailfish {
+        let tokens = read_tokens(line);
 
-    part2(content);
+        let mut snail = Snailfish::new();
+        snail.parse(&tokens, 0);
+
+        snail
+    }
+
+    #[test]
+    fn magnitude() {
+        let cases = [
+            ("[[1,2],[[3,4],5]]", 143),
+            ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+            ("[[[[1,1],[2,2]],[3,3]],[4,4]]", 445),
+            ("[[[[3,0],[5,3]],[4,4]],[5,5]]", 791),
+            ("[[[[5,0],[7,4]],[5,5]],[6,6]]", 1137),
+            ("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]", 3488),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(snail(line).magnetude(0), expected, "{}", line);
+        }
+    }
+
+    // The two numbers summed in inputs/day18-example.txt
+    #[test]
+    fn add_reduces() {
+        let mut result = snail("[[[[4,3],4],4],[7,[[8,4],9]]]");
+        result.add(snail("[1,1]"));
+
+        let expected = snail("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+
+        assert_eq!(result.number, expected.number);
+        assert_eq!(result.magnetude(0), 1384);
+    }
+
+    #[test]
+    fn sum_of_list() {
+        let lines = ["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"];
+
+        let mut result = snail(lines[0]);
+
+        for line in &lines[1..] {
+            result.add(snail(line));
+        }
+
+        assert_eq!(result.number, snail("[[[[5,0],[7,4]],[5,5]],[6,6]]").number);
+    }
+
+    // The tree of day18v2 against the markers, after every addition
+    #[test]
+    fn same_sums_as_snail_numbers() {
+        use crate::day18v2::SnailNumber;
+
+        let examples = [
+            include_str!("../inputs/day18-example.txt"),
+            include_str!("../inputs/day18-example9.txt"),
+        ];
+
+        for content in examples {
+            let lines: Vec<&str> = content.lines().collect();
+
+            // Each line as the sum of its halves when they are pairs, the
+            // line of day18-example.txt isn't reduced
+            for line in &lines {
+                let number: SnailNumber = line.parse().unwrap();
+
+                if let SnailNumber::Pair(lhs, rhs) = number {
+                    if matches!(*lhs, SnailNumber::Regular(_)) || matches!(*rhs, SnailNumber::Regular(_)) {
+                        continue;
+                    }
+
+                    let mut flat = snail(&lhs.to_string());
+                    flat.add(snail(&rhs.to_string()));
+
+                    assert_eq!(flat.number, snail(&(*lhs + *rhs).to_string()).number, "{}", line);
+                }
+            }
+
+            let mut flat = snail(lines[0]);
+            let mut tree: SnailNumber = lines[0].parse().unwrap();
+
+            for line in &lines[1..] {
+                flat.add(snail(line));
+                tree = tree + line.parse().unwrap();
+
+                assert_eq!(flat.number, snail(&tree.to_string()).number, "{}", line);
+                assert_eq!(flat.magnetude(0), tree.magnitude());
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        let err = read_snails("[1,2]\n[[1,2],3\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 9, "", "expecting ']'"));
+
+        let err = read_snails("[1,x]").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 4, "x", "expecting a number or a pair"));
+
+        let err = read_snails("[1,2]]").err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (6, "]"));
+    }
 }
@@ -0,0 +1,617 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::json::Value;
+use crate::log;
+use crate::parse::{self, Line, ParseError};
+use crate::solution::{Answer, Export, Logger, Solution};
+
+// A regular number or a pair of snailfish numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnailNumber {
+    Regular(u32),
+    Pair(Box<SnailNumber>, Box<SnailNumber>),
+}
+
+use SnailNumber::*;
+
+impl SnailNumber {
+    pub fn pair(lhs: SnailNumber, rhs: SnailNumber) -> Self {
+        Pair(Box::new(lhs), Box::new(rhs))
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            Regular(n) => *n,
+            Pair(lhs, rhs) => 3 * lhs.magnitude() + 2 * rhs.magnitude(),
+        }
+    }
+
+    // Explodes and splits until neither applies, an explosion always goes
+    // before a split
+    pub fn reduce(&mut self) {
+        while self.reduce_step().is_some() {}
+    }
+
+    // Same as `reduce`, each action goes to `trace`
+    pub fn reduce_traced(&mut self, trace: &mut Vec<Step>) {
+        loop {
+            let before = self.to_string();
+
+            let Some((action, path)) = self.reduce_step() else {
+                break;
+            };
+
+            let offset = offset_of(&before, &path);
+
+            trace.push(Step { action, path, offset, before, after: self.to_string() });
+        }
+    }
+
+    // The next action of the reduction and where it happened, the sides taken
+    // from the root down to the pair or number that changed
+    fn reduce_step(&mut self) -> Option<(Action, String)> {
+        let mut path = String::new();
+
+        if self.explode(&mut path).is_some() {
+            return Some((Action::Explode, path));
+        }
+
+        path.clear();
+
+        if self.split(&mut path) {
+            return Some((Action::Split, path));
+        }
+
+        None
+    }
+
+    // Explodes the leftmost pair nested inside four pairs, `path` leads to it.
+    // Some with the values still to add to the regular numbers on its left
+    // and on its right once it did.
+    fn explode(&mut self, path: &mut String) -> Option<(Option<u32>, Option<u32>)> {
+        let (lhs, rhs) = match self {
+            Regular(_) => return None,
+            Pair(lhs, rhs) => (lhs, rhs),
+        };
+
+        if path.len() >= 4 {
+            if let (Regular(l), Regular(r)) = (lhs.as_ref(), rhs.as_ref()) {
+                let carry = (Some(*l), Some(*r));
+
+                *self = Regular(0);
+                return Some(carry);
+            }
+        }
+
+        path.push('L');
+
+        if let Some((left, right)) = lhs.explode(path) {
+            if let Some(value) = right {
+                rhs.add_leftmost(value);
+            }
+
+            return Some((left, None));
+        }
+
+        path.pop();
+        path.push('R');
+
+        if let Some((left, right)) = rhs.explode(path) {
+            if let Some(value) = left {
+                lhs.add_rightmost(value);
+            }
+
+            return Some((None, right));
+        }
+
+        path.pop();
+
+        None
+    }
+
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Regular(n) => *n += value,
+            Pair(lhs, _) => lhs.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Regular(n) => *n += value,
+            Pair(_, rhs) => rhs.add_rightmost(value),
+        }
+    }
+
+    // Splits the leftmost regular number of 10 or more, `path` leads to it
+    fn split(&mut self, path: &mut String) -> bool {
+        match self {
+            Regular(n) if *n >= 10 => {
+                *self = SnailNumber::pair(Regular(*n / 2), Regular(*n - *n / 2));
+                true
+            },
+            Regular(_) => false,
+            Pair(lhs, rhs) => {
+                path.push('L');
+
+                if lhs.split(path) {
+                    return true;
+                }
+
+                path.pop();
+                path.push('R');
+
+                if rhs.split(path) {
+                    return true;
+                }
+
+                path.pop();
+                false
+            },
+        }
+    }
+}
+
+// Offset in the text of a number of what `path` leads to, following the
+// brackets and commas
+fn offset_of(text: &str, path: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+
+    for side in path.chars() {
+        // Inside the pair
+        pos += 1;
+
+        if side == 'R' {
+            // Past the left element and its comma
+            let mut depth = 0;
+
+            while depth > 0 || bytes[pos] != b',' {
+                match bytes[pos] {
+                    b'[' => depth += 1,
+                    b']' => depth -= 1,
+                    _ => {},
+                }
+
+                pos += 1;
+            }
+
+            pos += 1;
+        }
+    }
+
+    pos
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    Addition,
+    Explode,
+    Split,
+}
+
+impl Action {
+    fn name(self) -> &'static str {
+        match self {
+            Action::Addition => "addition",
+            Action::Explode => "explode",
+            Action::Split => "split",
+        }
+    }
+}
+
+// What one action did to a number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub action: Action,
+
+    // Sides taken from the root, e.g. "LRR", and the offset in `before` of
+    // the pair or number acted upon. An addition acts on the whole number.
+    pub path: String,
+    pub offset: usize,
+
+    pub before: String,
+    pub after: String,
+}
+
+// Every addition of a sum and the actions reducing it
+pub fn trace_sum(numbers: &[SnailNumber]) -> Vec<Step> {
+    let mut trace = vec![];
+
+    let Some((first, rest)) = numbers.split_first() else {
+        return trace;
+    };
+
+    let mut total = first.clone();
+
+    for n in rest {
+        let before = total.to_string();
+
+        total = SnailNumber::pair(total, n.clone());
+
+        trace.push(Step {
+            action: Action::Addition,
+            path: String::new(),
+            offset: 0,
+            before,
+            after: total.to_string(),
+        });
+
+        total.reduce_traced(&mut trace);
+    }
+
+    trace
+}
+
+// One line per action, as in the worked example of the puzzle, e.g.
+// "after explode:  [[[[0,7],4],[7,[[8,4],9]]],[1,1]]"
+pub fn trace_text(trace: &[Step]) -> String {
+    trace
+        .iter()
+        .map(|step| format!("{:<16}{}\n", format!("after {}:", step.action.name()), step.after))
+        .collect()
+}
+
+pub fn trace_json(trace: &[Step]) -> Value {
+    let steps = trace
+        .iter()
+        .map(|step| {
+            Value::Object(vec![
+                ("action".to_string(), Value::from(step.action.name())),
+                ("path".to_string(), Value::from(step.path.as_str())),
+                ("offset".to_string(), Value::from(step.offset)),
+                ("before".to_string(), Value::from(step.before.as_str())),
+                ("after".to_string(), Value::from(step.after.as_str())),
+            ])
+        })
+        .collect();
+
+    Value::Object(vec![("steps".to_string(), Value::Array(steps))])
+}
+
+impl fmt::Display for SnailNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Regular(n) => write!(f, "{}", n),
+            Pair(lhs, rhs) => write!(f, "[{},{}]", lhs, rhs),
+        }
+    }
+}
+
+// Reads a regular number or a pair from `pos` and returns the position right
+// after it
+fn read_element(line: &Line, pos: usize) -> Result<(SnailNumber, usize), ParseError> {
+    let bytes = line.text.as_bytes();
+
+    let expect = |pos: usize, c: u8| -> Result<usize, ParseError> {
+        if bytes.get(pos) == Some(&c) {
+            Ok(pos + 1)
+        } else {
+            Err(line.error_at(pos, 1, &format!("expecting '{}'", c as char)))
+        }
+    };
+
+    let digits = bytes[pos..].iter().take_while(|c| c.is_ascii_digit()).count();
+
+    if digits > 0 {
+        let n = line.parse::<u32>(&line.text[pos..pos + digits])?;
+        return Ok((Regular(n), pos + digits));
+    }
+
+    if bytes.get(pos) != Some(&b'[') {
+        return Err(line.error_at(pos, 1, "expecting a number or a pair"));
+    }
+
+    let (lhs, pos) = read_element(line, pos + 1)?;
+    let pos = expect(pos, b',')?;
+    let (rhs, pos) = read_element(line, pos)?;
+    let pos = expect(pos, b']')?;
+
+    Ok((SnailNumber::pair(lhs, rhs), pos))
+}
+
+// A whole line holding a pair
+fn read_line(line: &Line) -> Result<SnailNumber, ParseError> {
+    if !line.text.starts_with('[') {
+        return Err(line.error_at(0, 1, "expecting '['"));
+    }
+
+    let (number, end) = read_element(line, 0)?;
+
+    if end != line.text.len() {
+        return Err(line.error(&line.text[end..], "unexpected text after the number"));
+    }
+
+    Ok(number)
+}
+
+impl FromStr for SnailNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        read_line(&Line { number: 1, text: s })
+    }
+}
+
+// The reduced sum
+impl Add for SnailNumber {
+    type Output = SnailNumber;
+
+    fn add(self, other: SnailNumber) -> SnailNumber {
+        let mut sum = SnailNumber::pair(self, other);
+
+        sum.reduce();
+        sum
+    }
+}
+
+// Numbers added from the first to the last, there's no snailfish zero so the
+// iterator can't be empty
+impl Sum for SnailNumber {
+    fn sum<I: Iterator<Item = SnailNumber>>(mut iter: I) -> Self {
+        let first = iter.next().expect("a sum of snailfish numbers needs at least one of them");
+
+        iter.fold(first, |acc, n| acc + n)
+    }
+}
+
+fn read_numbers(content: &str) -> Result<Vec<SnailNumber>, ParseError> {
+    let numbers = parse::lines(content)
+        .map(|line| read_line(&line))
+        .collect::<Result<Vec<SnailNumber>, ParseError>>()?;
+
+    if numbers.is_empty() {
+        return Err(ParseError::end_of_input(content, "no snailfish numbers"));
+    }
+
+    Ok(numbers)
+}
+
+pub fn part1(content: &str) -> Result<Answer, ParseError> {
+    let numbers = read_numbers(content)?;
+
+    let total: SnailNumber = numbers.into_iter().sum();
+
+    Ok(Answer::from(total.magnitude()))
+}
+
+// Sum of two different numbers of a list, numbers[i] + numbers[j]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairSum {
+    pub i: usize,
+    pub j: usize,
+    pub sum: SnailNumber,
+    pub magnitude: u32,
+}
+
+// Largest magnitude of the sums numbers[i] + numbers[i'] for i' != i
+fn largest_in_row(numbers: &[SnailNumber], i: usize) -> Option<PairSum> {
+    let mut best: Option<PairSum> = None;
+
+    for (j, b) in numbers.iter().enumerate() {
+        if i != j {
+            let sum = numbers[i].clone() + b.clone();
+            let magnitude = sum.magnitude();
+
+            if best.as_ref().map_or(true, |best| magnitude > best.magnitude) {
+                best = Some(PairSum { i, j, sum, magnitude });
+            }
+        }
+    }
+
+    best
+}
+
+// The two different numbers whose sum has the largest magnitude, the first
+// pair in the order of the list on a tie. The threads take the rows of the
+// pairs one at a time, so none of them waits while others have work left.
+// None with less than two numbers.
+pub fn largest_pair(numbers: &[SnailNumber], threads: usize) -> Option<PairSum> {
+    let next_row = AtomicUsize::new(0);
+
+    let bests: Vec<PairSum> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut bests = vec![];
+
+                    loop {
+                        let i = next_row.fetch_add(1, Ordering::Relaxed);
+
+                        if i >= numbers.len() {
+                            return bests;
+                        }
+
+                        bests.extend(largest_in_row(numbers, i));
+                    }
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().expect("a worker panicked"))
+            .collect()
+    });
+
+    bests
+        .into_iter()
+        .min_by_key(|p| (Reverse(p.magnitude), p.i, p.j))
+}
+
+fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let numbers = read_numbers(content)?;
+
+    let best = largest_pair(&numbers, available_threads())
+        .ok_or_else(|| ParseError::end_of_input(content, "at least two snailfish numbers are needed"))?;
+
+    log!(logger, "numbers {} and {} (line {} + line {})", best.i, best.j, best.i + 1, best.j + 1);
+    log!(logger, "sum: {}", best.sum);
+
+    Ok(Answer::from(best.magnitude))
+}
+
+// Reduction trace of the whole sum for part 1, of the sum of the largest
+// magnitude for part 2, as JSON and as text
+pub fn export(content: &str, part: u32) -> Result<Vec<Export>, ParseError> {
+    let numbers = read_numbers(content)?;
+
+    let trace = if part == 2 {
+        match largest_pair(&numbers, available_threads()) {
+            Some(best) => trace_sum(&[numbers[best.i].clone(), numbers[best.j].clone()]),
+            None => vec![],
+        }
+    } else {
+        trace_sum(&numbers)
+    };
+
+    Ok(vec![
+        Export::new("trace.json", trace_json(&trace).pretty()),
+        Export::new("trace.txt", trace_text(&trace)),
+    ])
+}
+
+pub struct Day18v2;
+
+impl Solution for Day18v2 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_numbers(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, _logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+
+    fn export(&self, content: &str, part: u32) -> Option<Result<Vec<Export>, ParseError>> {
+        Some(export(content, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(s: &str) -> SnailNumber {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn magnitude() {
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+        assert_eq!(number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(), 3488);
+    }
+
+    #[test]
+    fn explosions() {
+        let cases = [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            ("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]", "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"),
+        ];
+
+        for (before, after) in cases {
+            let mut n = number(before);
+
+            assert!(n.explode(&mut String::new()).is_some(), "{}", before);
+            assert_eq!(n.to_string(), after);
+        }
+    }
+
+    #[test]
+    fn addition() {
+        let sum = number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]");
+        assert_eq!(sum.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+
+        let total: SnailNumber = ["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]
+            .into_iter()
+            .map(number)
+            .sum();
+
+        assert_eq!(total.to_string(), "[[[[5,0],[7,4]],[5,5]],[6,6]]");
+    }
+
+    // The worked example of the puzzle
+    #[test]
+    fn reduction_trace() {
+        let trace = trace_sum(&[number("[[[[4,3],4],4],[7,[[8,4],9]]]"), number("[1,1]")]);
+
+        assert_eq!(trace_text(&trace), "\
+after addition: [[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]
+after explode:  [[[[0,7],4],[7,[[8,4],9]]],[1,1]]
+after explode:  [[[[0,7],4],[15,[0,13]]],[1,1]]
+after split:    [[[[0,7],4],[[7,8],[0,13]]],[1,1]]
+after split:    [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]
+after explode:  [[[[0,7],4],[[7,8],[6,0]]],[8,1]]
+");
+
+        let acted_on: Vec<(&str, &str)> = trace[1..]
+            .iter()
+            .map(|step| (step.path.as_str(), &step.before[step.offset..step.offset + 5]))
+            .collect();
+
+        assert_eq!(acted_on, [
+            ("LLLL", "[4,3]"),
+            ("LRRL", "[8,4]"),
+            ("LRL", "15,[0"),
+            ("LRRR", "13]]]"),
+            ("LRRR", "[6,7]"),
+        ]);
+
+        let json = trace_json(&trace);
+        let steps = json.get("steps").unwrap().as_array().unwrap();
+
+        assert_eq!(steps.len(), 6);
+        assert_eq!(steps[2].get("action").unwrap().as_str(), Some("explode"));
+        assert_eq!(steps[2].get("offset").unwrap().as_f64(), Some(16.0));
+    }
+
+    #[test]
+    fn largest_pair_on_threads() {
+        let numbers = read_numbers(include_str!("../inputs/day18-example9.txt")).unwrap();
+
+        let best = largest_pair(&numbers, 1).unwrap();
+
+        assert_eq!((best.i, best.j, best.magnitude), (8, 0, 3993));
+        assert_eq!(best.sum.to_string(), "[[[[7,8],[6,6]],[[6,0],[7,7]]],[[[7,8],[8,8]],[[7,9],[0,6]]]]");
+
+        for threads in [2, 3, 16] {
+            assert_eq!(largest_pair(&numbers, threads).as_ref(), Some(&best));
+        }
+
+        // Every sum of two equal numbers is a tie, the first pair wins
+        let same = vec![number("[1,2]"); 4];
+        let best = largest_pair(&same, 4).unwrap();
+
+        assert_eq!((best.i, best.j), (0, 1));
+        assert_eq!(largest_pair(&same[..1], 4), None);
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        let err = read_numbers("[1,2]\n[[1,2],3\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 9, "", "expecting ']'"));
+
+        let err = "[1,x]".parse::<SnailNumber>().unwrap_err();
+        assert_eq!(err, ParseError::new(1, 4, "x", "expecting a number or a pair"));
+
+        let err = "[1,2]]".parse::<SnailNumber>().unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "]"));
+
+        assert_eq!("7".parse::<SnailNumber>().unwrap_err().reason, "expecting '['");
+    }
+}
@@ -0,0 +1,755 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+
+use crate::image::heat_color;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Export, Logger, Solution};
+
+type Coord = (i32, i32, i32);
+
+fn add(a: Coord, b: Coord) -> Coord {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+fn sub(a: Coord, b: Coord) -> Coord {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn manhattan_distance(a: &(i32, i32, i32), b: &(i32, i32, i32)) -> i32 {
+    (b.0 - a.0).abs() + (b.1 - a.1).abs() + (b.2 - a.2).abs()
+}
+
+// One of the 24 ways a scanner can be turned, as a matrix with a single 1
+// or -1 on each row and a determinant of 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rotation([[i32; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    // The whole group, the identity first
+    pub fn all() -> Vec<Rotation> {
+        let permutations = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+        let mut rotations = vec![];
+
+        for axes in permutations {
+            for signs in 0..8 {
+                let mut m = [[0; 3]; 3];
+
+                for row in 0..3 {
+                    m[row][axes[row]] = if signs >> row & 1 == 1 { -1 } else { 1 };
+                }
+
+                let rotation = Rotation(m);
+
+                // The other half are mirror images
+                if rotation.determinant() == 1 {
+                    rotations.push(rotation);
+                }
+            }
+        }
+
+        rotations
+    }
+
+    fn determinant(&self) -> i32 {
+        let m = &self.0;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn apply(&self, c: Coord) -> Coord {
+        let row = |r: &[i32; 3]| r[0] * c.0 + r[1] * c.1 + r[2] * c.2;
+
+        (row(&self.0[0]), row(&self.0[1]), row(&self.0[2]))
+    }
+
+    // `other` first, then this one
+    pub fn compose(&self, other: &Rotation) -> Rotation {
+        let mut m = [[0; 3]; 3];
+
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] = (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum();
+            }
+        }
+
+        Rotation(m)
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        let m = &self.0;
+
+        Rotation([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+}
+
+// Where each axis comes from, e.g. "-y, +x, +z" for a quarter turn around z
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self
+            .0
+            .iter()
+            .map(|row| {
+                let axis = row.iter().position(|&v| v != 0).unwrap();
+                let sign = if row[axis] > 0 { '+' } else { '-' };
+
+                format!("{}{}", sign, ['x', 'y', 'z'][axis])
+            })
+            .collect();
+
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+// How scanners are matched: the beacons two scanners must both see, and how
+// far apart two sightings of a beacon can be once the scanners are aligned.
+// The puzzle's coordinates are exact, noisy ones need some tolerance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Matching {
+    pub overlap: usize,
+    pub epsilon: i32,
+}
+
+impl Matching {
+    pub const PUZZLE: Matching = Matching { overlap: 12, epsilon: 0 };
+
+    fn near(&self, a: Coord, b: Coord) -> bool {
+        squared_length(sub(a, b)) <= (self.epsilon as i64).pow(2)
+    }
+}
+
+fn squared_length((x, y, z): Coord) -> i64 {
+    (x as i64).pow(2) + (y as i64).pow(2) + (z as i64).pow(2)
+}
+
+// Distances between the beacons of a scanner, sorted. They don't depend on
+// where the scanner is or how it's turned, so two scanners seeing the same
+// beacons share their distances.
+fn fingerprint(beacons: &[Coord]) -> Vec<f64> {
+    let mut distances = vec![];
+
+    for (i, &a) in beacons.iter().enumerate() {
+        for &b in &beacons[i + 1..] {
+            distances.push((squared_length(sub(a, b)) as f64).sqrt());
+        }
+    }
+
+    distances.sort_unstable_by(|a, b| a.total_cmp(b));
+    distances
+}
+
+// Distances found in both sorted lists up to `tolerance`, counting repeats
+fn common_distances(a: &[f64], b: &[f64], tolerance: f64) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+
+    while i < a.len() && j < b.len() {
+        if (a[i] - b[j]).abs() <= tolerance {
+            count += 1;
+            i += 1;
+            j += 1;
+        } else if a[i] < b[j] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    count
+}
+
+// How the beacons of a second scanner map to those of the first:
+// rotation.apply(beacon) + offset, which is the second scanner's position
+// when the beacon is (0, 0, 0)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    pub rotation: Rotation,
+    pub offset: Coord,
+}
+
+// Tries every rotation of `second`, each pair of beacons votes for the
+// offset that would put them on top of each other
+pub fn align(first: &[Coord], second: &[Coord], matching: &Matching) -> Option<Alignment> {
+    if matching.epsilon > 0 {
+        return align_near(first, second, matching);
+    }
+
+    for rotation in Rotation::all() {
+        let mut votes: HashMap<Coord, usize> = HashMap::new();
+
+        for &b in second {
+            let rotated = rotation.apply(b);
+
+            for &a in first {
+                let offset = sub(a, rotated);
+                let count = votes.entry(offset).or_insert(0);
+
+                *count += 1;
+
+                if *count >= matching.overlap {
+                    return Some(Alignment { rotation, offset });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// With noise the offsets of matching beacons only fall close to each
+// other. They vote for cells as wide as the spread of their errors, the
+// offsets of a cell and its neighbors give a mean offset, which is kept
+// when enough beacons are near their match with it.
+fn align_near(first: &[Coord], second: &[Coord], matching: &Matching) -> Option<Alignment> {
+    let size = 2 * matching.epsilon + 1;
+    let cell = |(x, y, z): Coord| (x.div_euclid(size), y.div_euclid(size), z.div_euclid(size));
+
+    // The offsets of the matches are spread over 2 cells along each axis at
+    // most, one of the 8 gets an eighth of them
+    let densest = matching.overlap.div_ceil(8).max(1);
+
+    for rotation in Rotation::all() {
+        let rotated: Vec<Coord> = second.iter().map(|&b| rotation.apply(b)).collect();
+        let mut cells: HashMap<Coord, Vec<Coord>> = HashMap::new();
+
+        for &b in &rotated {
+            for &a in first {
+                let offset = sub(a, b);
+                cells.entry(cell(offset)).or_default().push(offset);
+            }
+        }
+
+        // The densest cells first, always in the same order
+        let mut candidates: Vec<(usize, Coord)> = cells
+            .iter()
+            .filter(|(_, offsets)| offsets.len() >= densest)
+            .map(|(&key, offsets)| (offsets.len(), key))
+            .collect();
+
+        candidates.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        for (_, (x, y, z)) in candidates {
+            let around: Vec<Coord> = (0..27)
+                .filter_map(|n| cells.get(&(x + n % 3 - 1, y + n / 3 % 3 - 1, z + n / 9 - 1)))
+                .flatten()
+                .copied()
+                .collect();
+
+            if around.len() < matching.overlap {
+                continue;
+            }
+
+            let n = around.len() as f64;
+            let mean = |f: fn(&Coord) -> i32| (around.iter().map(|c| f(c) as f64).sum::<f64>() / n).round() as i32;
+            let offset = (mean(|c| c.0), mean(|c| c.1), mean(|c| c.2));
+
+            let matches = rotated
+                .iter()
+                .filter(|&&b| first.iter().any(|&a| matching.near(a, add(b, offset))))
+                .count();
+
+            if matches >= matching.overlap {
+                return Some(Alignment { rotation, offset });
+            }
+        }
+    }
+
+    None
+}
+
+// Position and rotation of a scanner relative to the first one, `parent`
+// is the scanner it was aligned with
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Scanner {
+    pub position: Coord,
+    pub rotation: Rotation,
+    pub parent: Option<usize>,
+}
+
+impl Scanner {
+    // Coordinates of a beacon it sees, relative to the first scanner
+    pub fn locate(&self, beacon: Coord) -> Coord {
+        add(self.rotation.apply(beacon), self.position)
+    }
+}
+
+pub struct ScannerMap {
+    pub scanners: Vec<Scanner>,
+
+    // Each beacon and the first scanner that sees it
+    pub beacons: BTreeMap<Coord, usize>,
+}
+
+// Places the scanners from the first one, each aligned with a scanner
+// already placed. Only the pairs sharing enough distances are aligned. The
+// error is a scanner that doesn't overlap with the others.
+pub fn map_scanners(scanners: &[Vec<Coord>], matching: &Matching, logger: &Logger) -> Result<ScannerMap, usize> {
+    let fingerprints: Vec<Vec<f64>> = scanners.iter().map(|beacons| fingerprint(beacons)).collect();
+    let likely = matching.overlap * matching.overlap.saturating_sub(1) / 2;
+
+    // Both ends of a distance can be off by epsilon
+    let tolerance = 2.0 * matching.epsilon as f64;
+
+    let mut placed: Vec<Option<Scanner>> = vec![None; scanners.len()];
+    let mut queue = VecDeque::from([0]);
+
+    placed[0] = Some(Scanner {
+        position: (0, 0, 0),
+        rotation: Rotation::IDENTITY,
+        parent: None,
+    });
+
+    while let Some(i) = queue.pop_front() {
+        let from = placed[i].unwrap();
+
+        for j in 0..scanners.len() {
+            if placed[j].is_some() {
+                continue;
+            }
+
+            let common = common_distances(&fingerprints[i], &fingerprints[j], tolerance);
+
+            if common < likely {
+                continue;
+            }
+
+            let Some(alignment) = align(&scanners[i], &scanners[j], matching) else {
+                log!(logger, "scanners {} and {} share {} distances but don't line up", i, j, common);
+                continue;
+            };
+
+            let scanner = Scanner {
+                position: from.locate(alignment.offset),
+                rotation: from.rotation.compose(&alignment.rotation),
+                parent: Some(i),
+            };
+
+            log!(logger, "scanner {} at {:?}, turned {} (from scanner {})", j, scanner.position, scanner.rotation, i);
+
+            placed[j] = Some(scanner);
+            queue.push_back(j);
+        }
+    }
+
+    if let Some(k) = placed.iter().position(|s| s.is_none()) {
+        return Err(k);
+    }
+
+    let scanners_placed: Vec<Scanner> = placed.into_iter().map(|s| s.unwrap()).collect();
+    let mut beacons = BTreeMap::new();
+
+    for (k, scanner) in scanners_placed.iter().enumerate() {
+        for &beacon in &scanners[k] {
+            let beacon = scanner.locate(beacon);
+
+            // A beacon already seen by another scanner, a little off
+            if matching.epsilon > 0 && beacons.keys().any(|&b| matching.near(b, beacon)) {
+                continue;
+            }
+
+            beacons.entry(beacon).or_insert(k);
+        }
+    }
+
+    Ok(ScannerMap {
+        scanners: scanners_placed,
+        beacons,
+    })
+}
+
+// Beacons of each scanner and the line of the scanner header
+fn read_scanners(content: &str) -> Result<(Vec<Vec<Coord>>, Vec<parse::Line<'_>>), ParseError> {
+    let mut scanners_beacons: Vec<Vec<Coord>> = vec![];
+    let mut headers = vec![];
+
+    for line in parse::lines(content) {
+        let _line = line.text.trim();
+
+        if _line.len() == 0 {
+            continue;
+        }
+
+        if _line.starts_with("--- ") {
+            scanners_beacons.push(vec![]);
+            headers.push(line);
+            continue;
+        }
+
+        let beacons = scanners_beacons
+            .last_mut()
+            .ok_or_else(|| line.error(_line, "beacon before the first scanner"))?;
+
+        let coords = _line
+            .split(",")
+            .map(|num_str| line.parse::<i32>(num_str))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
+
+        if coords.len() != 3 {
+            return Err(line.error(_line, "expecting a beacon as x,y,z"));
+        }
+
+        beacons.push((coords[0], coords[1], coords[2]));
+    }
+
+    if scanners_beacons.is_empty() {
+        return Err(ParseError::end_of_input(content, "no scanners found"));
+    }
+
+    Ok((scanners_beacons, headers))
+}
+
+fn align_scanners(content: &str, logger: &Logger) -> Result<ScannerMap, ParseError> {
+    let (scanners_beacons, headers) = read_scanners(content)?;
+
+    map_scanners(&scanners_beacons, &Matching::PUZZLE, logger).map_err(|k| {
+        let header = &headers[k];
+        header.error(header.text, "scanner doesn't overlap with the others")
+    })
+}
+
+// Colors far apart for scanners next to each other in the input
+fn scanner_color(k: usize) -> [u8; 3] {
+    heat_color((k as f64 * 0.618_034).fract())
+}
+
+const SCANNER_COLOR: [u8; 3] = [255, 255, 255];
+
+// A row for each scanner then for each beacon, with the scanner that saw
+// the beacon first
+pub fn to_csv(map: &ScannerMap) -> String {
+    let mut csv = String::from("kind,x,y,z,scanner\n");
+
+    for (k, s) in map.scanners.iter().enumerate() {
+        csv.push_str(&format!("scanner,{},{},{},{}\n", s.position.0, s.position.1, s.position.2, k));
+    }
+
+    for (&(x, y, z), &k) in &map.beacons {
+        csv.push_str(&format!("beacon,{},{},{},{}\n", x, y, z, k));
+    }
+
+    csv
+}
+
+// Point cloud with the scanners in white and the beacons in the color of
+// the scanner that saw them first
+pub fn to_ply(map: &ScannerMap) -> String {
+    let mut ply = String::from("ply\nformat ascii 1.0\n");
+
+    ply.push_str(&format!("comment {} scanners then {} beacons\n", map.scanners.len(), map.beacons.len()));
+    ply.push_str(&format!("element vertex {}\n", map.scanners.len() + map.beacons.len()));
+
+    for property in ["float x", "float y", "float z", "uchar red", "uchar green", "uchar blue"] {
+        ply.push_str(&format!("property {}\n", property));
+    }
+
+    ply.push_str("end_header\n");
+
+    let vertices = map
+        .scanners
+        .iter()
+        .map(|s| (s.position, SCANNER_COLOR))
+        .chain(map.beacons.iter().map(|(&b, &k)| (b, scanner_color(k))));
+
+    for ((x, y, z), [r, g, b]) in vertices {
+        ply.push_str(&format!("{} {} {} {} {} {}\n", x, y, z, r, g, b));
+    }
+
+    ply
+}
+
+// Same points as the PLY file, an object for the scanners and one for the
+// beacons each scanner saw first. The vertex colors are an extension most
+// viewers read.
+pub fn to_obj(map: &ScannerMap) -> String {
+    let mut obj = format!("# {} scanners, {} beacons\n", map.scanners.len(), map.beacons.len());
+    let mut count = 0;
+
+    let mut object = |obj: &mut String, name: &str, points: &[Coord], [r, g, b]: [u8; 3]| {
+        obj.push_str(&format!("o {}\n", name));
+
+        for &(x, y, z) in points {
+            obj.push_str(&format!("v {} {} {} {:.3} {:.3} {:.3}\n", x, y, z, r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0));
+        }
+
+        let indices: Vec<String> = (count + 1..=count + points.len()).map(|i| i.to_string()).collect();
+        obj.push_str(&format!("p {}\n", indices.join(" ")));
+
+        count += points.len();
+    };
+
+    let positions: Vec<Coord> = map.scanners.iter().map(|s| s.position).collect();
+    object(&mut obj, "scanners", &positions, SCANNER_COLOR);
+
+    for k in 0..map.scanners.len() {
+        let beacons: Vec<Coord> = map.beacons.iter().filter(|&(_, &first)| first == k).map(|(&b, _)| b).collect();
+
+        if !beacons.is_empty() {
+            object(&mut obj, &format!("scanner{}", k), &beacons, scanner_color(k));
+        }
+    }
+
+    obj
+}
+
+// The map of the scanners is the same for both parts
+pub fn export(content: &str) -> Result<Vec<Export>, ParseError> {
+    let map = align_scanners(content, &Logger::silent())?;
+
+    Ok(vec![
+        Export::new("beacons.ply", to_ply(&map)),
+        Export::new("beacons.obj", to_obj(&map)),
+        Export::new("beacons.csv", to_csv(&map)),
+    ])
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let map = align_scanners(content, logger)?;
+
+    log!(logger, "count: {}", map.beacons.len());
+
+    Ok(Answer::from(map.beacons.len()))
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let map = align_scanners(content, logger)?;
+    let scanners_positions: Vec<Coord> = map.scanners.iter().map(|s| s.position).collect();
+
+    let mut max_distance: i32 = 0;
+
+    for j in 0..scanners_positions.len() {
+        for k in j+1..scanners_positions.len() {
+            let d = manhattan_distance(&scanners_positions[j], &scanners_positions[k]);
+
+            if d > max_distance {
+                max_distance = d;
+            }
+        }
+
+    }
+
+    log!(logger, "max manhattan distance: {}", max_distance);
+
+    Ok(Answer::from(max_distance))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_scanners(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+
+    fn export(&self, content: &str, _part: u32) -> Option<Result<Vec<Export>, ParseError>> {
+        Some(export(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_group() {
+        let all = Rotation::all();
+
+        assert_eq!(all.len(), 24);
+        assert_eq!(all[0], Rotation::IDENTITY);
+
+        for a in &all {
+            assert!(all.contains(&a.inverse()));
+            assert_eq!(a.compose(&a.inverse()), Rotation::IDENTITY);
+
+            for b in &all {
+                assert!(all.contains(&a.compose(b)));
+                assert_eq!(a.compose(b).apply((1, 2, 3)), a.apply(b.apply((1, 2, 3))));
+            }
+        }
+
+        // A quarter turn around z
+        let turn = Rotation([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+        assert_eq!(turn.apply((1, 2, 3)), (-2, 1, 3));
+        assert_eq!(turn.to_string(), "-y, +x, +z");
+    }
+
+    #[test]
+    fn fingerprints() {
+        let beacons = [(0, 0, 0), (1, 2, 2), (3, 0, 4), (-1, 5, 2)];
+        let distances = fingerprint(&beacons);
+
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[0], 3.0);
+
+        // The same beacons seen from elsewhere
+        let turn = Rotation::all()[17];
+        let moved: Vec<Coord> = beacons.iter().map(|&b| add(turn.apply(b), (50, -7, 3))).collect();
+
+        assert_eq!(fingerprint(&moved), distances);
+        assert_eq!(common_distances(&[1.0, 4.0, 4.0, 9.0], &[4.0, 4.0, 4.0, 9.0, 10.0], 0.0), 3);
+        assert_eq!(common_distances(&[1.0, 4.0, 4.0, 9.0], &[4.5, 8.0, 10.0], 1.0), 2);
+    }
+
+    #[test]
+    fn scanner_map() {
+        let (scanners, _) = read_scanners(include_str!("../inputs/day19-example.txt")).unwrap();
+        let map = map_scanners(&scanners, &Matching::PUZZLE, &Logger::silent()).unwrap();
+
+        let positions: Vec<Coord> = map.scanners.iter().map(|s| s.position).collect();
+        assert_eq!(positions, [(0, 0, 0), (68, -1246, -43), (1105, -1205, 1229), (-92, -2380, -20), (-20, -1133, 1061)]);
+
+        assert_eq!(map.scanners[0].parent, None);
+        assert_eq!(map.scanners[2].parent, Some(4));
+        assert_eq!(map.beacons.len(), 79);
+
+        // Every beacon a scanner sees is on the map
+        for (k, scanner) in map.scanners.iter().enumerate() {
+            for &beacon in &scanners[k] {
+                assert!(map.beacons.get(&scanner.locate(beacon)).is_some_and(|&first| first <= k));
+            }
+        }
+
+        assert_eq!(map.beacons.get(&(-618, -824, -621)), Some(&0));
+        assert_eq!(map.beacons.values().filter(|&&first| first == 0).count(), scanners[0].len());
+    }
+
+    #[test]
+    fn alignment_of_a_turned_copy() {
+        let (scanners, _) = read_scanners(include_str!("../inputs/day19-example.txt")).unwrap();
+
+        for rotation in Rotation::all() {
+            let offset = (-300, 25, 1200);
+
+            // What a scanner at `offset` turned by `rotation` would see
+            let seen: Vec<Coord> = scanners[0]
+                .iter()
+                .map(|&b| rotation.inverse().apply(sub(b, offset)))
+                .collect();
+
+            assert_eq!(align(&scanners[0], &seen, &Matching::PUZZLE), Some(Alignment { rotation, offset }));
+        }
+
+        assert_eq!(align(&scanners[0], &scanners[2], &Matching::PUZZLE), None);
+    }
+
+    // Scanners 1000 apart along x, each turned its own way and seeing the
+    // beacons up to 1000 away along each axis, a unit off along each axis
+    fn noisy_scanners(seed: u64) -> (Vec<Scanner>, usize, Vec<Vec<Coord>>) {
+        let mut seed = seed;
+        let mut random = |range: i32| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % range as u64) as i32
+        };
+
+        let beacons: Vec<Coord> = (0..500)
+            .map(|_| (random(6000) - 1500, random(3000) - 1500, random(3000) - 1500))
+            .collect();
+
+        let rotations = Rotation::all();
+        let mut truth = vec![];
+        let mut seen = vec![false; beacons.len()];
+        let mut sightings = vec![];
+
+        for k in 0..4 {
+            let scanner = Scanner {
+                position: if k == 0 { (0, 0, 0) } else { (1000 * k + random(100), random(100), random(100)) },
+                rotation: if k == 0 { Rotation::IDENTITY } else { rotations[random(24) as usize] },
+                parent: None,
+            };
+
+            let mut scanner_sightings = vec![];
+
+            for (i, &b) in beacons.iter().enumerate() {
+                let (x, y, z) = sub(b, scanner.position);
+
+                if x.abs() <= 1000 && y.abs() <= 1000 && z.abs() <= 1000 {
+                    let noise = (random(3) - 1, random(3) - 1, random(3) - 1);
+
+                    scanner_sightings.push(add(scanner.rotation.inverse().apply((x, y, z)), noise));
+                    seen[i] = true;
+                }
+            }
+
+            truth.push(scanner);
+            sightings.push(scanner_sightings);
+        }
+
+        (truth, seen.iter().filter(|&&s| s).count(), sightings)
+    }
+
+    #[test]
+    fn noisy_alignment() {
+        let logger = Logger::silent();
+
+        for seed in [0x2545f4914f6cdd1d, 0x9e3779b97f4a7c15, 12345] {
+            let (truth, count, sightings) = noisy_scanners(seed);
+
+            // Exact matching gives up, a little tolerance lines them up
+            assert!(map_scanners(&sightings, &Matching::PUZZLE, &logger).is_err());
+
+            let matching = Matching { overlap: 12, epsilon: 6 };
+            let map = map_scanners(&sightings, &matching, &logger).unwrap();
+
+            for (found, expected) in map.scanners.iter().zip(&truth) {
+                assert_eq!(found.rotation, expected.rotation);
+                assert!(manhattan_distance(&found.position, &expected.position) <= 6, "{:?} {:?}", found, expected);
+            }
+
+            assert_eq!(map.beacons.len(), count);
+
+            // Asking for more beacons than the scanners share
+            let strict = Matching { overlap: 200, epsilon: 6 };
+            assert!(map_scanners(&sightings, &strict, &logger).is_err());
+        }
+    }
+
+    #[test]
+    fn exported_map() {
+        let files = export(include_str!("../inputs/day19-example.txt")).unwrap();
+        let text = |name: &str| String::from_utf8(files.iter().find(|f| f.name == name).unwrap().data.clone()).unwrap();
+
+        let ply = text("beacons.ply");
+        assert!(ply.contains("element vertex 84\n"));
+        assert!(ply.contains("end_header\n0 0 0 255 255 255\n68 -1246 -43 255 255 255\n"));
+        assert_eq!(ply.lines().count(), 11 + 84);
+
+        // The scanners are vertices 1 to 5, the beacons follow scanner by scanner
+        let obj = text("beacons.obj");
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 84);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("o ")).count(), 6);
+        assert!(obj.contains("p 1 2 3 4 5\no scanner0\n"));
+        assert!(obj.trim_end().ends_with(" 84"));
+
+        let csv = text("beacons.csv");
+        assert_eq!(csv.lines().count(), 1 + 5 + 79);
+        assert!(csv.starts_with("kind,x,y,z,scanner\nscanner,0,0,0,0\n"));
+        assert!(csv.contains("\nbeacon,-618,-824,-621,0\n"));
+    }
+
+    #[test]
+    fn scanner_without_overlap() {
+        let mut scanners = vec![vec![(0, 0, 0), (1, 1, 1)]; 2];
+        scanners[1].push((5, 5, 5));
+
+        assert_eq!(map_scanners(&scanners, &Matching::PUZZLE, &Logger::silent()).err(), Some(1));
+
+        let content = "--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6\n";
+        let e = align_scanners(content, &Logger::silent()).err().unwrap();
+        assert_eq!(e.to_string(), "<input>:4:1: scanner doesn't overlap with the others '--- scanner 1 ---'");
+    }
+}
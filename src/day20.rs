@@ -0,0 +1,457 @@
+use std::fmt;
+
+use crate::grid::Grid;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+// An image on an infinite canvas: the pixels of a window, and the state of
+// the background everywhere around it
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfiniteImage {
+    pixels: Grid<char>,
+    background: char,
+}
+
+impl InfiniteImage {
+    // The puzzle's images start on a dark background
+    pub fn new(pixels: Grid<char>) -> Self {
+        InfiniteImage {
+            pixels,
+            background: '.',
+        }
+    }
+
+    pub fn pixels(&self) -> &Grid<char> {
+        &self.pixels
+    }
+
+    pub fn background(&self) -> char {
+        self.background
+    }
+
+    // Pixel anywhere on the canvas, (0, 0) is the top left corner of the window
+    pub fn get(&self, i: isize, j: isize) -> char {
+        if i < 0 || j < 0 {
+            return self.background;
+        }
+
+        self.pixels.get((i as usize, j as usize)).copied().unwrap_or(self.background)
+    }
+
+    // The window grows by a pixel on each side, the pixels further out only
+    // see background and all turn into the same pixel, the new background
+    pub fn enhance(&self, enhancement: &[u8]) -> InfiniteImage {
+        let pixels = Grid::from_fn(self.pixels.nrows() + 2, self.pixels.ncols() + 2, |(i, j)| {
+            let mut value: usize = 0;
+
+            for fi in i as isize - 2..=i as isize {
+                for fj in j as isize - 2..=j as isize {
+                    value = value << 1 | (self.get(fi, fj) == '#') as usize;
+                }
+            }
+
+            enhancement[value] as char
+        });
+
+        let background = if self.background == '#' { enhancement[511] } else { enhancement[0] };
+
+        InfiniteImage {
+            pixels,
+            background: background as char,
+        }
+    }
+
+    // None when the background is lit, infinitely many pixels are
+    pub fn lit(&self) -> Option<usize> {
+        if self.background == '#' {
+            return None;
+        }
+
+        Some(self.pixels.values().filter(|&&c| c == '#').count())
+    }
+}
+
+impl fmt::Display for InfiniteImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}background: {}", self.pixels, self.background)
+    }
+}
+
+// An enhancement for packed images, two pixels at a time: 4 columns of 3
+// rows hold both neighborhoods
+pub struct PairRule {
+    pairs: Vec<u64>,
+
+    // What the background turns into
+    dark_background: bool,
+    lit_background: bool,
+}
+
+impl PairRule {
+    pub fn new(enhancement: &[u8]) -> Self {
+        // The leftmost column comes out of the words as the lowest bit of
+        // each row, the enhancement wants it as the highest
+        let lit = |t: usize, m: usize, b: usize| {
+            let reversed = |row: usize| (row & 1) << 2 | row & 2 | row >> 2;
+            (enhancement[reversed(t) << 6 | reversed(m) << 3 | reversed(b)] == b'#') as u64
+        };
+
+        let pairs = (0..1 << 12)
+            .map(|index| {
+                let (t, m, b) = (index >> 8, index >> 4 & 15, index & 15);
+                lit(t & 7, m & 7, b & 7) | lit(t >> 1, m >> 1, b >> 1) << 1
+            })
+            .collect();
+
+        PairRule {
+            pairs,
+            dark_background: enhancement[0] == b'#',
+            lit_background: enhancement[511] == b'#',
+        }
+    }
+}
+
+// The same image with each row packed in u64 words, a bit for each pixel
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedImage {
+    nrows: usize,
+    ncols: usize,
+
+    // Words of a row, the pixel of column j is bit j % 64 of word j / 64
+    stride: usize,
+    bits: Vec<u64>,
+
+    background: bool,
+}
+
+impl PackedImage {
+    pub fn new(image: &InfiniteImage) -> Self {
+        let (nrows, ncols) = (image.pixels.nrows(), image.pixels.ncols());
+        let stride = ncols.div_ceil(64);
+        let mut bits = vec![0; nrows * stride];
+
+        for ((i, j), &c) in image.pixels.iter() {
+            if c == '#' {
+                bits[i * stride + j / 64] |= 1 << (j % 64);
+            }
+        }
+
+        PackedImage {
+            nrows,
+            ncols,
+            stride,
+            bits,
+            background: image.background == '#',
+        }
+    }
+
+    pub fn to_image(&self) -> InfiniteImage {
+        let pixel = |lit: bool| if lit { '#' } else { '.' };
+
+        InfiniteImage {
+            pixels: Grid::from_fn(self.nrows, self.ncols, |(i, j)| pixel(self.bits[i * self.stride + j / 64] >> (j % 64) & 1 == 1)),
+            background: pixel(self.background),
+        }
+    }
+
+    // A row shifted right by 2 columns of background, and as much
+    // background after it as `len` words hold. The rows around the window
+    // are all background.
+    fn padded_row(&self, i: isize, len: usize) -> Vec<u64> {
+        let fill = if self.background { u64::MAX } else { 0 };
+
+        if i < 0 || i as usize >= self.nrows {
+            return vec![fill; len];
+        }
+
+        let row = &self.bits[i as usize * self.stride..(i as usize + 1) * self.stride];
+
+        // Word k of the row, background past its last pixel
+        let word = |k: isize| -> u64 {
+            if k < 0 || k as usize >= self.stride {
+                return fill;
+            }
+
+            let pixels = self.ncols - k as usize * 64;
+            let mask = if pixels >= 64 { u64::MAX } else { (1 << pixels) - 1 };
+
+            row[k as usize] & mask | fill & !mask
+        };
+
+        (0..len as isize).map(|k| word(k) << 2 | word(k - 1) >> 62).collect()
+    }
+
+    // Same as InfiniteImage::enhance. Output pixel j sees the input columns
+    // j - 2 to j, bits j to j + 2 of the padded rows: shifting the words of
+    // the three rows right gives the neighborhoods of a pair of pixels in turn.
+    pub fn enhance(&self, rule: &PairRule) -> PackedImage {
+        let (nrows, ncols) = (self.nrows + 2, self.ncols + 2);
+        let stride = ncols.div_ceil(64);
+        let mut bits = vec![0; nrows * stride];
+
+        // Input rows -2 to nrows - 1, with a word to spare on the right
+        let rows: Vec<Vec<u64>> = (-2..nrows as isize).map(|i| self.padded_row(i, stride + 1)).collect();
+
+        for i in 0..nrows {
+            let (top, middle, bottom) = (&rows[i], &rows[i + 1], &rows[i + 2]);
+
+            for w in 0..stride {
+                let pixels = (ncols - w * 64).min(64);
+                let mut word = 0;
+
+                // The windows of the first 62 pixels are within the word, the
+                // last 2 reach into the next one
+                let windows = [(0, 62.min(pixels)), (62, pixels)];
+
+                for (start, end) in windows {
+                    let window = |row: &[u64]| row[w] >> start | if start > 0 { row[w + 1] << (64 - start) } else { 0 };
+                    let (mut t, mut m, mut b) = (window(top), window(middle), window(bottom));
+
+                    for bit in (start..end).step_by(2) {
+                        let index = (t as usize & 15) << 8 | (m as usize & 15) << 4 | b as usize & 15;
+                        word |= rule.pairs[index] << bit;
+
+                        t >>= 2;
+                        m >>= 2;
+                        b >>= 2;
+                    }
+                }
+
+                // The second pixel of the last pair can be past the end
+                if pixels < 64 {
+                    word &= (1 << pixels) - 1;
+                }
+
+                bits[i * stride + w] = word;
+            }
+        }
+
+        let background = if self.background { rule.lit_background } else { rule.dark_background };
+
+        PackedImage {
+            nrows,
+            ncols,
+            stride,
+            bits,
+            background,
+        }
+    }
+
+    // None when the background is lit
+    pub fn lit(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+
+        Some(self.bits.iter().map(|w| w.count_ones() as usize).sum())
+    }
+}
+
+// Enhancement algorithm, an empty line and the image rows
+pub fn read_image(content: &str) -> Result<(&[u8], Grid<char>), ParseError> {
+    let is_pixel = |c: char| c == '#' || c == '.';
+
+    let mut lines = parse::lines(content);
+
+    let first = parse::first_line(content)?;
+    lines.next();
+
+    if let Some((i, c)) = first.text.char_indices().find(|&(_, c)| !is_pixel(c)) {
+        return Err(first.error_at(i, c.len_utf8(), "not a pixel"));
+    }
+
+    if first.text.len() != 512 {
+        return Err(first.error(first.text, "enhancement must have 512 pixels"));
+    }
+
+    let enhancement = first.text.as_bytes();
+
+    // An image lit up to infinity never turns dark again
+    if enhancement[0] == b'#' && enhancement[511] == b'#' {
+        return Err(first.error_at(511, 1, "enhancement keeps the infinite image lit"));
+    }
+
+    match lines.next() {
+        Some(line) if line.text.is_empty() => (),
+        Some(line) => return Err(line.error(line.text, "expecting an empty line")),
+        None => return Err(ParseError::end_of_input(content, "missing the image")),
+    }
+
+    let inputs = Grid::from_lines(lines, content, |c| Some(c).filter(|&c| is_pixel(c)), "not a pixel")?;
+
+    Ok((enhancement, inputs))
+}
+
+// The enhancement is applied `steps` times
+fn lit_after(content: &str, steps: usize, logger: &Logger) -> Result<Answer, ParseError> {
+    let (enhancement, inputs) = read_image(content)?;
+
+    let rule = PairRule::new(enhancement);
+    let mut image = PackedImage::new(&InfiniteImage::new(inputs));
+
+    for _ in 0..steps {
+        image = image.enhance(&rule);
+    }
+
+    // Only rendered when someone reads it
+    if logger.enabled() && steps <= 2 {
+        log!(logger, "{}", image.to_image());
+    }
+
+    let lit = image.lit().expect("read_image rejects enhancements keeping the background lit");
+
+    log!(logger, "lit pixels after {} steps: {}", steps, lit);
+
+    Ok(Answer::from(lit))
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    lit_after(content, 2, logger)
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    lit_after(content, 50, logger)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_image(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lights a pixel when its neighborhood is all dark and turns it off when
+    // all lit, otherwise keeps the center pixel. This flips the infinite
+    // background at every step, like the real input does.
+    fn flipping_enhancement() -> String {
+        (0..512)
+            .map(|i| {
+                if i == 0 {
+                    '#'
+                } else if i == 511 {
+                    '.'
+                } else if i & 0x10 != 0 {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn enhancement_starting_with_lit_pixel() {
+        let content = format!("{}\n\n#\n", flipping_enhancement());
+
+        // After two steps: the lit pixel, a dark ring around it and a lit ring
+        // of 16 pixels, the background is dark again.
+        assert_eq!(part1(&content, &Logger::silent()), Ok(Answer::from(17)));
+    }
+
+    #[test]
+    fn enhancement_starting_with_dark_pixel() {
+        let enhancement: String = (0..512)
+            .map(|i| if i & 0x10 != 0 { '#' } else { '.' })
+            .collect();
+
+        let content = format!("{}\n\n#..\n.#.\n..#\n", enhancement);
+
+        assert_eq!(part1(&content, &Logger::silent()), Ok(Answer::from(3)));
+        assert_eq!(part2(&content, &Logger::silent()), Ok(Answer::from(3)));
+    }
+
+    #[test]
+    fn background_of_any_enhancement() {
+        let (_, pixels) = read_image(include_str!("../inputs/day20-example.txt")).unwrap();
+
+        let flipping = flipping_enhancement();
+        let keeping: String = (0..512).map(|i| if i == 0 { '.' } else { '#' }).collect();
+        let lit = "#".repeat(512);
+
+        let backgrounds = |enhancement: &str| -> String {
+            let mut image = InfiniteImage::new(pixels.clone());
+
+            (0..4)
+                .map(|_| {
+                    image = image.enhance(enhancement.as_bytes());
+                    image.background()
+                })
+                .collect()
+        };
+
+        assert_eq!(backgrounds(&flipping), "#.#.");
+        assert_eq!(backgrounds(&keeping), "....");
+        assert_eq!(backgrounds(&lit), "####");
+
+        let image = InfiniteImage::new(pixels.clone()).enhance(lit.as_bytes());
+        assert_eq!(image.lit(), None);
+        assert_eq!(image.get(-100, 3), '#');
+    }
+
+    #[test]
+    fn example_step_by_step() {
+        let (enhancement, pixels) = read_image(include_str!("../inputs/day20-example.txt")).unwrap();
+        let mut image = InfiniteImage::new(pixels);
+
+        image = image.enhance(enhancement).enhance(enhancement);
+
+        // The window grows by a pixel on each side at every step
+        assert_eq!((image.pixels().nrows(), image.pixels().ncols()), (9, 9));
+        assert_eq!(image.pixels().row(0).iter().collect::<String>(), ".......#.");
+        assert_eq!(image.get(9, 9), '.');
+        assert_eq!(image.lit(), Some(35));
+    }
+
+    #[test]
+    fn packed_images() {
+        let (enhancement, pixels) = read_image(include_str!("../inputs/day20.txt")).unwrap();
+        let wide = Grid::from_fn(7, 130, |(i, j)| if (i * 7 + j * 3) % 5 < 2 { '#' } else { '.' });
+
+        for pixels in [pixels, wide] {
+            let rule = PairRule::new(enhancement);
+            let mut image = InfiniteImage::new(pixels);
+            let mut packed = PackedImage::new(&image);
+
+            assert_eq!(packed.to_image(), image);
+
+            // The real enhancement flips the background at every step
+            for _ in 0..4 {
+                image = image.enhance(enhancement);
+                packed = packed.enhance(&rule);
+
+                assert_eq!(packed.to_image(), image);
+                assert_eq!(packed.lit(), image.lit());
+            }
+        }
+
+        let lit = "#".repeat(512);
+        let image = InfiniteImage::new(Grid::new(1, 70, '.'));
+        assert_eq!(PackedImage::new(&image).enhance(&PairRule::new(lit.as_bytes())).to_image(), image.enhance(lit.as_bytes()));
+    }
+
+    #[test]
+    fn always_lit_enhancement_is_rejected() {
+        let enhancement = "#".repeat(512);
+        let content = format!("{}\n\n#\n", enhancement);
+
+        let err = part2(&content, &Logger::silent()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 512));
+
+        let err = part2(&format!("{}\n\n#.\n#\n", flipping_enhancement()), &Logger::silent()).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+    }
+}
@@ -1,9 +1,30 @@
-// Example
-// const P1_START: usize = 4;
-// const P2_START: usize = 8;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+// "Player 1 starting position: 4"
+fn read_start_positions(content: &str) -> Result<(usize, usize), ParseError> {
+    let positions: Vec<usize> = parse::lines(content)
+        .map(|line| {
+            let prefix = format!("Player {} starting position: ", line.number);
+
+            let value = line.text
+                .strip_prefix(prefix.as_str())
+                .ok_or_else(|| line.error(line.text, &format!("expecting '{}'", prefix)))?;
+
+            match line.parse::<usize>(value.trim())? {
+                position @ 1..=10 => Ok(position),
+                _ => Err(line.error(value, "position out of 1..=10")),
+            }
+        })
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+
+    if positions.len() != 2 {
+        return Err(ParseError::end_of_input(content, "expecting the positions of two players"));
+    }
 
-const P1_START: usize = 6;
-const P2_START: usize = 10;
+    Ok((positions[0], positions[1]))
+}
 
 // Deterministic dice
 #[derive(Debug)]
@@ -83,9 +104,11 @@ impl Player {
     }
 }
 
-fn part1() {
-    let mut p1 = Player::new(P1_START);
-    let mut p2 = Player::new(P2_START);
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (p1_start, p2_start) = read_start_positions(content)?;
+
+    let mut p1 = Player::new(p1_start);
+    let mut p2 = Player::new(p2_start);
 
     let mut dice = DDice::new(100);
 
@@ -104,9 +127,9 @@ fn part1() {
         }
     }
 
-    println!("{:?}", p1);
-    println!("{:?}", p2);
-    println!("{:?}", dice);
+    log!(logger, "{:?}", p1);
+    log!(logger, "{:?}", p2);
+    log!(logger, "{:?}", dice);
 
     let lowest_score =
         if p1.score < p2.score {
@@ -115,7 +138,9 @@ fn part1() {
             p2.score
         };
 
-    println!("\nresult: {}", dice.roll_count * lowest_score);
+    log!(logger, "result: {}", dice.roll_count * lowest_score);
+
+    Ok(Answer::from(dice.roll_count * lowest_score))
 }
 
 #[derive(Debug)]
@@ -126,7 +151,7 @@ struct UniverseScore {
 
 // sum         | 3 | 4 | 5 | 6 | 7 | 8 | 9
 // # universes | 1 | 3 | 6 | 7 | 6 | 3 | 1
-const score_table: [UniverseScore; 7] = [
+const SCORE_TABLE: [UniverseScore; 7] = [
     UniverseScore { value: 3, count: 1 },
     UniverseScore { value: 4, count: 3 },
     UniverseScore { value: 5, count: 6 },
@@ -136,13 +161,16 @@ const score_table: [UniverseScore; 7] = [
     UniverseScore { value: 9, count: 1 },
 ];
 
+#[allow(dead_code)]
 fn player_compare(p1: &Player, p2: &Player) -> bool {
    p1.position == p2.position && p1.score == p2.score && p1.play_count == p2.play_count
 }
 
-fn part2() {
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let (p1_start, p2_start) = read_start_positions(content)?;
+
     let mut players_universes: Vec<(Player, Player)> = vec![
-        (Player::new(P1_START), Player::new(P2_START))
+        (Player::new(p1_start), Player::new(p2_start))
     ];
 
     // let mut players_universes_wins: Vec<(Player, Player)> = vec![];
@@ -154,7 +182,7 @@ fn part2() {
         let mut new_universes: Vec<(Player, Player)> = vec![];
 
         for p in &players_universes {
-            for score in score_table {
+            for score in SCORE_TABLE {
                 let mut new_player1 = p.0.make_copy();
 
                 new_player1.play_with_sum(score.value, score.count);
@@ -173,9 +201,9 @@ fn part2() {
 
         players_universes = new_universes;
 
-        println!("p1 plays ({})", k);
-        println!("  p1 wins: {}", player1_wins);
-        println!("  p2 wins: {}", player2_wins);
+        log!(logger, "p1 plays ({})", k);
+        log!(logger, "  p1 wins: {}", player1_wins);
+        log!(logger, "  p2 wins: {}", player2_wins);
 
         if players_universes.len() == 0 {
             break;
@@ -184,7 +212,7 @@ fn part2() {
         new_universes = vec![];
 
         for p in &players_universes {
-            for score in score_table {
+            for score in SCORE_TABLE {
                 let mut new_player2 = p.1.make_copy();
 
                 new_player2.play_with_sum(score.value, score.count);
@@ -203,17 +231,30 @@ fn part2() {
 
         players_universes = new_universes;
 
-        println!("p2 plays ({})", k);
-        println!("  p1 wins: {}", player1_wins);
-        println!("  p2 wins: {}", player2_wins);
+        log!(logger, "p2 plays ({})", k);
+        log!(logger, "  p1 wins: {}", player1_wins);
+        log!(logger, "  p2 wins: {}", player2_wins);
 
         if players_universes.len() == 0 {
             break;
         }
     }
+
+    Ok(Answer::from(player1_wins.max(player2_wins)))
 }
 
-fn main() {
-    // part1();
-    part2();
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_start_positions(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
 }
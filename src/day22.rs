@@ -1,4 +1,6 @@
-use std::fs;
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
 
 #[derive(Copy, Clone, Debug)]
 struct Range {
@@ -23,25 +25,28 @@ fn is_digit(c: u8) -> bool {
     b'0' <= c && c <= b'9'
 }
 
-fn tokenize(line: &str) -> Vec<Token> {
+// Tokens with the byte range they were read from
+fn tokenize(line: &parse::Line) -> Result<Vec<(Token, usize, usize)>, ParseError> {
     let mut tokens = vec![];
 
-    let chars = line.as_bytes();
+    let chars = line.text.as_bytes();
 
     let mut i = 0;
 
     while i < chars.len() {
-        let mut c = chars[i];
+        let c = chars[i];
 
-        while c == b' ' {
+        if c == b' ' {
             i += 1;
-            c = chars[i];
+            continue;
         }
 
+        let start = i;
+
         let token = match c {
             b'.' => {
-                if chars[i + 1] != b'.' {
-                    panic!("next char is not a '.'");
+                if chars.get(i + 1) != Some(&b'.') {
+                    return Err(line.error_at(i, 2, "expecting '..'"));
                 }
 
                 i += 2;
@@ -60,19 +65,15 @@ fn tokenize(line: &str) -> Vec<Token> {
             },
             _ => {
                 if is_alpha(c) {
-                    let start = i;
-
                     while i < chars.len() && is_alpha(chars[i]) {
                         i += 1;
                     }
 
-                    Token::Ident(String::from_utf8(chars[start..i].to_vec()).unwrap())
+                    Token::Ident(line.text[start..i].to_string())
                 } else if c == b'-' || is_digit(c) {
-                    let start = i;
-
                     if c == b'-' {
                         if i + 1 >= chars.len() || !is_digit(chars[i + 1]) {
-                            panic!("next char is not a digit");
+                            return Err(line.error_at(i, 2, "expecting a digit after '-'"));
                         }
 
                         i += 1;
@@ -82,17 +83,19 @@ fn tokenize(line: &str) -> Vec<Token> {
                         i += 1;
                     }
 
-                    Token::Number(i64::from_str_radix(&line[start..i], 10).unwrap())
+                    Token::Number(line.parse::<i64>(&line.text[start..i])?)
                 } else {
-                    unreachable!();
+                    let len = line.text[i..].chars().next().map_or(1, |c| c.len_utf8());
+
+                    return Err(line.error_at(i, len, "unexpected character"));
                 }
             }
         };
 
-        tokens.push(token);
+        tokens.push((token, start, i));
     }
 
-    tokens
+    Ok(tokens)
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -103,7 +106,8 @@ struct Cuboid {
     z_range: Range,
 }
 
-fn read_line_cuboid(line: &str) -> Cuboid {
+// "on x=-20..26,y=-36..17,z=-47..7", every axis given once
+fn read_line_cuboid(line: &parse::Line) -> Result<Cuboid, ParseError> {
     let mut cuboid = Cuboid {
         on: false,
         x_range: Range{ start: 0, end: 0 },
@@ -111,86 +115,114 @@ fn read_line_cuboid(line: &str) -> Cuboid {
         z_range: Range{ start: 0, end: 0 },
     };
 
-    let tokens = tokenize(&line);
+    let tokens = tokenize(line)?;
 
     // println!("{:?}", tokens);
 
-    if let Token::Ident(state) = &tokens[0] {
-        cuboid.on = state == "on";
-    } else {
-        unreachable!();
+    let error_at = |i: usize, reason: &str| -> ParseError {
+        match tokens.get(i) {
+            Some(&(_, start, end)) => line.error_at(start, end - start, reason),
+            None => line.error_at(line.text.len(), 0, reason),
+        }
+    };
+
+    let expect_number = |i: usize| -> Result<i64, ParseError> {
+        match tokens.get(i) {
+            Some(&(Token::Number(n), _, _)) => Ok(n),
+            _ => Err(error_at(i, "expecting a number")),
+        }
+    };
+
+    let expect = |i: usize, token: Token, reason: &str| -> Result<(), ParseError> {
+        match tokens.get(i) {
+            Some((t, _, _)) if *t == token => Ok(()),
+            _ => Err(error_at(i, reason)),
+        }
+    };
+
+    match tokens.first() {
+        Some((Token::Ident(state), _, _)) if state == "on" || state == "off" => {
+            cuboid.on = state == "on";
+        },
+        _ => return Err(error_at(0, "expecting 'on' or 'off'")),
     }
 
+    let mut seen = [false; 3];
     let mut i = 1;
 
     while i < tokens.len() {
-        let axis: &str = 
-            if let Token::Ident(state) = &tokens[i] {
-                state.as_str()
-            } else {
-                unreachable!("expecting an ident");
+        let axis: usize =
+            match &tokens[i].0 {
+                Token::Ident(axis) if axis == "x" => 0,
+                Token::Ident(axis) if axis == "y" => 1,
+                Token::Ident(axis) if axis == "z" => 2,
+                _ => return Err(error_at(i, "expecting axis to be x, y or z")),
             };
 
+        if seen[axis] {
+            return Err(error_at(i, "axis given more than once"));
+        }
+
+        seen[axis] = true;
+
         i += 1;
 
-        if tokens[i] != Token::Equal {
-            panic!("expecting an '='");
-        }
+        expect(i, Token::Equal, "expecting an '='")?;
 
         i += 1;
 
-        let start: i64 = 
-            if let Token::Number(n) = &tokens[i] {
-                *n
-            } else {
-                unreachable!("expecting a number");
-            };
+        let start = expect_number(i)?;
 
         i += 1;
 
-        if tokens[i] != Token::DotDot {
-            panic!("expecting a '..'");
-        }
+        expect(i, Token::DotDot, "expecting a '..'")?;
 
         i += 1;
 
-        let end: i64 = 
-            if let Token::Number(n) = &tokens[i] {
-                *n
-            } else {
-                unreachable!("expecting a number");
-            };
+        let end = expect_number(i)?;
+
+        if start > end {
+            return Err(error_at(i, "range end is before its start"));
+        }
 
         i += 1;
 
         if i < tokens.len() {
-            if tokens[i] != Token::Comma {
-                panic!("expecting a ','");
-            }
+            expect(i, Token::Comma, "expecting a ','")?;
 
             i += 1;
+
+            if i == tokens.len() {
+                return Err(error_at(i, "expecting an axis after ','"));
+            }
         }
 
+        let range = Range { start, end };
+
         match axis {
-            "x" => {
-                cuboid.x_range.start = start;
-                cuboid.x_range.end = end;
-            },
-            "y" => {
-                cuboid.y_range.start = start;
-                cuboid.y_range.end = end;
-            },
-            "z" => {
-                cuboid.z_range.start = start;
-                cuboid.z_range.end = end;
-            },
-            _ => {
-                unreachable!("expecting axis to be x, y or z");
-            },
+            0 => cuboid.x_range = range,
+            1 => cuboid.y_range = range,
+            _ => cuboid.z_range = range,
         }
     }
 
-    cuboid
+    if seen != [true; 3] {
+        return Err(error_at(tokens.len(), "expecting the x, y and z ranges"));
+    }
+
+    Ok(cuboid)
+}
+
+fn read_cuboids(content: &str) -> Result<Vec<Cuboid>, ParseError> {
+    let cuboids = parse::lines(content)
+        .map(|line| read_line_cuboid(&line))
+        .collect::<Result<Vec<Cuboid>, ParseError>>()?;
+
+    if cuboids.is_empty() {
+        return Err(ParseError::end_of_input(content, "no reboot steps"));
+    }
+
+    Ok(cuboids)
 }
 
 fn clamp(v: i64, min: i64, max: i64) -> i64 {
@@ -218,14 +250,8 @@ enum CubeState {
     Off,
 }
 
-#[allow(dead_code)]
-fn part1(content: String) {
-    let cuboids: Vec<Cuboid> = content
-        .lines()
-        .map(|line| {
-            read_line_cuboid(&line)
-        })
-        .collect();
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let cuboids: Vec<Cuboid> = read_cuboids(content)?;
 
     // println!("{:?}", cuboids);
 
@@ -293,9 +319,11 @@ fn part1(content: String) {
         }
     }
 
-    println!("on:      {}", on_count);
-    println!("off:     {}", off_count);
-    println!("not set: {}", not_set_count);
+    log!(logger, "on:      {}", on_count);
+    log!(logger, "off:     {}", off_count);
+    log!(logger, "not set: {}", not_set_count);
+
+    Ok(Answer::from(on_count))
 }
 
 fn min(a: i64, b: i64) -> i64 {
@@ -454,13 +482,8 @@ fn cuboids_intersect(c1: &Cuboid, c2: &Cuboid) -> Option<Cuboid> {
     Some(r)
 }
 
-fn part2(content: String) {
-    let cuboids: Vec<Cuboid> = content
-        .lines()
-        .map(|line| {
-            read_line_cuboid(&line)
-        })
-        .collect();
+pub fn part2(content: &str) -> Result<Answer, ParseError> {
+    let cuboids: Vec<Cuboid> = read_cuboids(content)?;
 
     let mut subcuboids = vec![];
 
@@ -495,17 +518,55 @@ fn part2(content: String) {
         count += cuboid_cube_count(&c);
     }
 
-    println!("count: {}", count);
+    Ok(Answer::from(count))
 }
 
-fn main() {
-    // let filename = "inputs/day22-example2.txt";
-    let filename = "inputs/day22.txt";
+pub struct Day22;
 
-    let data = fs::read_to_string(filename)
-        .expect("Could read file");
+impl Solution for Day22 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_cuboids(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
 
-    // part1(data);
-    part2(data);
+    fn part2(&self, content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content))
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(content: &str) -> (usize, String, String) {
+        let err = read_cuboids(content).err().unwrap();
+
+        (err.column, err.text, err.reason)
+    }
+
+    #[test]
+    fn malformed_steps() {
+        let e = |col: usize, text: &str, reason: &str| (col, text.to_string(), reason.to_string());
+
+        assert_eq!(error("on x=1.2,y=1..2,z=1..2"), e(7, ".2", "expecting '..'"));
+        assert_eq!(error("on x=1..2,y=-a..2,z=1..2"), e(13, "-a", "expecting a digit after '-'"));
+        assert_eq!(error("on x=1..2;y=1..2,z=1..2"), e(10, ";", "unexpected character"));
+        assert_eq!(error("toggle x=1..2,y=1..2,z=1..2"), e(1, "toggle", "expecting 'on' or 'off'"));
+        assert_eq!(error("on x=1..2,w=1..2,z=1..2"), e(11, "w", "expecting axis to be x, y or z"));
+        assert_eq!(error("on x=1..2,y=1..2"), e(17, "", "expecting the x, y and z ranges"));
+        assert_eq!(error("on x=1..2,y=1..2,z=1.."), e(23, "", "expecting a number"));
+        assert_eq!(error("on x=3..2,y=1..2,z=1..2"), e(9, "2", "range end is before its start"));
+    }
+
+    #[test]
+    fn well_formed_step() {
+        let cuboids = read_cuboids("off x=-5..-1, y=0..3,z=2..2\n").unwrap();
+
+        assert!(!cuboids[0].on);
+        assert_eq!((cuboids[0].x_range.start, cuboids[0].x_range.end), (-5, -1));
+        assert_eq!((cuboids[0].z_range.start, cuboids[0].z_range.end), (2, 2));
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::fmt;
+
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::search::{self, Graph};
+use crate::solution::{Answer, Logger, Solution};
 
 const STAYING_POSITIONS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
 
@@ -55,42 +60,23 @@ impl Step {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Path {
-  cost: usize,
-  steps: Vec<Step>,
-}
-
-struct PathManager {
-    current_path: Path,
-  
-    min_cost: usize,
-    min_cost_path: Option<Path>,
+// The hallway, then the rows of the rooms
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Burrow {
     fields: Vec<[char; 11]>,
-
-    tracked: HashMap<String, usize>,
-    tracked_stack: Vec<Vec<Step>>,
-    tracked_stack_costs: Vec<Option<usize>>,
 }
 
-impl PathManager {
-    fn new(fields: Vec<[char; 11]>) -> Self {
-        let path = Path {
-            cost: 0,
-            steps: vec![],
-        };
-
-        PathManager {
-            current_path: path,
-            min_cost: 1 << 32,
-            min_cost_path: None,
-            fields: fields,
-            tracked: HashMap::new(),
-            tracked_stack: vec![],
-            tracked_stack_costs: vec![],
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.fields {
+            writeln!(f, "{}", line.iter().collect::<String>())?;
         }
+
+        Ok(())
     }
+}
 
+impl Burrow {
     fn fields_swap(&mut self, p1: &Position, p2: &Position) {
         let element = self.fields[p1.row][p1.col];
         self.fields[p1.row][p1.col] = self.fields[p2.row][p2.col];
@@ -101,72 +87,14 @@ impl PathManager {
         self.fields[p.row][p.col]
     }
 
-    fn fields_print(&self) {
-        let strs: Vec<String> = self.fields
-            .iter()
-            .map(|line| line.iter().collect())
-            .collect();
-
-        for s in strs {
-            println!("{}", s);
-        }
-        println!("");
-    }
-
-    fn fields_to_string(&self) -> String {
-        self.fields
-            .iter()
-            .map(|line| line.iter().collect())
-            .collect::<Vec<String>>()
-            .join("")
-    }
-
-    fn follow_path(&mut self) {
-        let mut total_cost = 0;
-
-        self.fields_print();
-
-        let steps = self.min_cost_path.as_ref().unwrap().steps.clone();
-
-        let size = steps.len();
-        let mut last_idx = 0;
-
-        for i in 0..size {
-            last_idx = i;
-            let step = steps[i];
-
-            let can_go_cost = self.anphipod_can_go_to(&step.start, &step.end);
-
-            if let Some(cost) = can_go_cost {
-                total_cost += cost;
-                self.fields_swap(&step.start, &step.end);
-
-                println!("cost: {} {}", cost, total_cost);
-                self.fields_print();
-            } else {
-                println!("error when following step {:?} {:?}", step.start, step.end);
-                break;
-            }
-        }
-
-        for i in (0..=last_idx).rev() {
-            let step = steps[i];
-            self.fields_swap(&step.start, &step.end);
-        }
-
-        println!("total_cost: {}", total_cost);
-    }
-
     fn anphipod_can_go_to(&self, start: &Position, end: &Position) -> Option<usize> {
         if start == end {
-            println!("Warn(anphipod_can_go_to): start and end are equal {:?}", start);
             return None;
         }
 
         let space = self.fields_at(start);
 
         if space < 'A' || space > 'D' {
-            println!("Warn(anphipod_can_go_to): invalid space {}", space);
             return None;
         }
 
@@ -372,134 +300,150 @@ impl PathManager {
         return true;
     }
 
-    fn exec(&mut self) -> Option<usize> {
-        let next_steps_idx = {
-            let joined = self.fields_to_string();
+    // Energy still needed by each amphipod to walk into its room, as if
+    // nothing was in its way
+    fn min_energy_left(&self) -> usize {
+        let total_rows = self.fields.len();
+        let mut energy = 0;
 
-            if !self.tracked.contains_key(&joined) {
-                let idx = self.tracked_stack.len();
-                self.tracked.insert(joined.clone(), idx);
+        for (row, line) in self.fields.iter().enumerate() {
+            for (col, &space) in line.iter().enumerate() {
+                if space < 'A' || space > 'D' {
+                    continue;
+                }
 
-                let new_steps = self.eval_step();
-                self.tracked_stack.push(new_steps);
-                self.tracked_stack_costs.push(None);
+                let room = room_by_anphipod(space);
 
-                idx
-            } else {
-                *self.tracked.get(&joined).unwrap()
+                let settled = col == room && (row + 1..total_rows).all(|r| self.fields[r][col] == space);
+
+                let steps =
+                    if row == 0 {
+                        col.abs_diff(room) + 1
+                    } else if settled {
+                        0
+                    } else if col == room {
+                        // Out of the room, aside and back in
+                        row + 3
+                    } else {
+                        row + col.abs_diff(room) + 1
+                    };
+
+                energy += steps * energy_by_anphipod(space);
             }
-        };
-
-        if self.tracked_stack[next_steps_idx].len() == 0 {
-            if self.check() {
-
-                if self.min_cost > self.current_path.cost {
-                    println!("path cost: {}", self.current_path.cost);
-                    self.min_cost = self.current_path.cost;
-                    self.min_cost_path = Some(self.current_path.clone());
-                }
+        }
 
-                return Some(0);
-            }
+        energy
+    }
+}
 
-            return None;
-        }
+// Every position of the amphipods, one move apart
+struct Moves;
 
-        let mut it_min_cost = self.tracked_stack_costs[next_steps_idx];
+impl Graph for Moves {
+    type Node = Burrow;
 
-        if let Some(min_tracked_cost) = it_min_cost {
-            if self.current_path.cost + min_tracked_cost > self.min_cost {
-                return it_min_cost;
-            }
-        }
+    fn neighbors(&self, burrow: &Burrow) -> Vec<(Burrow, u64)> {
+        burrow
+            .eval_step()
+            .into_iter()
+            .map(|step| {
+                let mut next = burrow.clone();
+                next.fields_swap(&step.start, &step.end);
 
-        let size = self.tracked_stack[next_steps_idx].len();
+                (next, step.cost as u64)
+            })
+            .collect()
+    }
 
-        for i in (0..size).rev() {
-            let step = self.tracked_stack[next_steps_idx][i];
+    fn heuristic(&self, burrow: &Burrow) -> u64 {
+        burrow.min_energy_left() as u64
+    }
+}
 
-            self.fields_swap(&step.start, &step.end);
+// Least energy to organize the amphipods
+fn organize(content: &str, fields: Vec<[char; 11]>, logger: &Logger) -> Result<usize, ParseError> {
+    let path = search::astar(&Moves, Burrow { fields }, Burrow::check)
+        .ok_or_else(|| ParseError::end_of_input(content, "the amphipods can't be organized"))?;
 
-            self.current_path.cost += step.cost;
-            self.current_path.steps.push(step); 
+    for burrow in &path.nodes {
+        log!(logger, "{}", burrow);
+    }
 
-            if let Some(new_min) = self.exec() {
-                if let Some(min_tracked_cost) = it_min_cost {
-                    if min_tracked_cost > new_min + step.cost {
-                        it_min_cost = Some(new_min + step.cost);
-                    }
-                } else {
-                    it_min_cost = Some(new_min + step.cost);
-                }
-            } else {
-                self.tracked_stack[next_steps_idx].swap_remove(i);
-            }
+    log!(logger, "min cost: {}", path.cost);
 
-            self.current_path.cost -= step.cost;
-            self.current_path.steps.pop(); 
+    Ok(path.cost as usize)
+}
 
-            self.fields_swap(&step.start, &step.end);
+// Reads the burrow diagram, e.g.
+// #############
+// #...........#
+// ###B#C#B#D###
+//   #A#D#C#A#
+//   #########
+fn read_fields(content: &str) -> Result<Vec<[char; 11]>, ParseError> {
+    let mut fields: Vec<[char; 11]> = vec![['.'; 11]];
+    let mut counts = [0; 4];
+
+    for line in parse::lines(content).skip(2) {
+        let chars: Vec<char> = line.text.chars().collect();
+
+        if chars.len() < 10 || chars[3] == '#' {
+            continue;
         }
 
-        self.tracked_stack_costs[next_steps_idx] = it_min_cost;
+        let mut row = [' '; 11];
 
-        return it_min_cost;
-    }
-}
+        for col in (2..10).step_by(2) {
+            let c = chars[col + 1];
 
-fn part1() {
-    // let fields: Vec<[char; 11]> = vec![
-    //     //  0    1    2    3    4    5    6    7    8    9   10
-    //     ['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
-    //     [' ', ' ', 'B', ' ', 'C', ' ', 'B', ' ', 'D', ' ', ' '],
-    //     [' ', ' ', 'A', ' ', 'D', ' ', 'C', ' ', 'A', ' ', ' '],
-    // ];
+            if !('A'..='D').contains(&c) {
+                return Err(line.error_at(col + 1, c.len_utf8(), "expecting an amphipod in A..=D"));
+            }
 
-    let fields: Vec<[char; 11]> = vec![
-        //  0    1    2    3    4    5    6    7    8    9   10
-        ['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
-        [' ', ' ', 'D', ' ', 'D', ' ', 'C', ' ', 'C', ' ', ' '],
-        [' ', ' ', 'B', ' ', 'A', ' ', 'B', ' ', 'A', ' ', ' '],
-    ];
+            counts[(c as u8 - b'A') as usize] += 1;
+            row[col] = c;
+        }
 
-    let mut manager = PathManager::new(fields);
+        fields.push(row);
+    }
 
-    manager.exec();
+    if fields.len() != 3 || counts != [2; 4] {
+        return Err(ParseError::end_of_input(content, "expecting two rooms rows with two amphipods of each type"));
+    }
 
-    manager.follow_path();
+    Ok(fields)
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let fields = read_fields(content)?;
 
-    println!("min cost: {}", manager.min_cost);
+    Ok(Answer::from(organize(content, fields, logger)?))
 }
 
-fn part2() {
-    // let fields: Vec<[char; 11]> = vec![
-    //     //  0    1    2    3    4    5    6    7    8    9   10
-    //     ['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
-    //     [' ', ' ', 'B', ' ', 'C', ' ', 'B', ' ', 'D', ' ', ' '],
-    //     [' ', ' ', 'D', ' ', 'C', ' ', 'B', ' ', 'A', ' ', ' '],
-    //     [' ', ' ', 'D', ' ', 'B', ' ', 'A', ' ', 'C', ' ', ' '],
-    //     [' ', ' ', 'A', ' ', 'D', ' ', 'C', ' ', 'A', ' ', ' '],
-    // ];
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut fields = read_fields(content)?;
 
-    let fields: Vec<[char; 11]> = vec![
-        //  0    1    2    3    4    5    6    7    8    9   10
-        ['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
-        [' ', ' ', 'D', ' ', 'D', ' ', 'C', ' ', 'C', ' ', ' '],
-        [' ', ' ', 'D', ' ', 'C', ' ', 'B', ' ', 'A', ' ', ' '],
-        [' ', ' ', 'D', ' ', 'B', ' ', 'A', ' ', 'C', ' ', ' '],
-        [' ', ' ', 'B', ' ', 'A', ' ', 'B', ' ', 'A', ' ', ' '],
-    ];
+    // The unfolded diagram has two extra lines between the first and last rows:
+    //   #D#C#B#A#
+    //   #D#B#A#C#
+    fields.insert(2, [' ', ' ', 'D', ' ', 'C', ' ', 'B', ' ', 'A', ' ', ' ']);
+    fields.insert(3, [' ', ' ', 'D', ' ', 'B', ' ', 'A', ' ', 'C', ' ', ' ']);
 
-    let mut manager = PathManager::new(fields);
+    Ok(Answer::from(organize(content, fields, logger)?))
+}
 
-    manager.exec();
+pub struct Day23;
 
-    manager.follow_path();
+impl Solution for Day23 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_fields(content).map(|_| ())
+    }
 
-    println!("min cost: {}", manager.min_cost);
-}
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
 
-fn main() {
-    // part1();
-    part2();
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
 }
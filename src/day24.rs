@@ -0,0 +1,947 @@
+use std::collections::{HashSet, HashMap};
+use std::fmt;
+
+use crate::log;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Logger, Solution};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operand {
+    Empty,
+    Var(char),
+    Value(i64),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operator {
+    Inp,
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Instruction(pub Operator, pub char, pub Operand);
+
+impl Operator {
+    fn name(self) -> &'static str {
+        match self {
+            Operator::Inp => "inp",
+            Operator::Add => "add",
+            Operator::Mul => "mul",
+            Operator::Div => "div",
+            Operator::Mod => "mod",
+            Operator::Eql => "eql",
+        }
+    }
+}
+
+// Source text of the operand, nothing for the missing operand of `inp`
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Empty => Ok(()),
+            Operand::Var(name) => write!(f, "{}", name),
+            Operand::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+// Source text of the instruction, e.g. "add x -1"
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Instruction(op, var1, var2) = self;
+
+        match var2 {
+            Operand::Empty => write!(f, "{} {}", op.name(), var1),
+            _ => write!(f, "{} {} {}", op.name(), var1, var2),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AluErrorKind {
+    DivisionByZero,
+
+    // `mod a b` with a < 0 or b <= 0
+    InvalidModulo,
+
+    MissingInput,
+    Overflow,
+}
+
+// Instruction the ALU couldn't run, the puzzle leaves the program undefined
+// from there
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AluError {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub kind: AluErrorKind,
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.kind {
+            AluErrorKind::DivisionByZero => "division by zero",
+            AluErrorKind::InvalidModulo => "modulo of a negative number or by a number under 1",
+            AluErrorKind::MissingInput => "no input left to read",
+            AluErrorKind::Overflow => "overflow",
+        };
+
+        write!(f, "instruction {} ({}): {}", self.pc + 1, self.instruction, reason)
+    }
+}
+
+pub struct ALU {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+    pub w: i64,
+
+    instructions: Vec<Instruction>,
+}
+
+impl ALU {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        ALU {
+            x: 0,
+            y: 0,
+            z: 0,
+            w: 0,
+            instructions,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.x = 0;
+        self.y = 0;
+        self.z = 0;
+        self.w = 0;
+    }
+
+    fn load(&mut self, name: char, value: i64) {
+        match name {
+            'x' => self.x = value,
+            'y' => self.y = value,
+            'z' => self.z = value,
+            'w' => self.w = value,
+            _ => unreachable!(),
+        };
+    }
+
+    pub fn get(&self, name: char) -> i64 {
+        match name {
+            'x' => self.x,
+            'y' => self.y,
+            'z' => self.z,
+            'w' => self.w,
+            _ => unreachable!(),
+        }
+    }
+
+    // https://www.keiruaprod.fr/blog/2021/12/29/a-comprehensive-guide-to-aoc-2021-day-24.html
+    fn solve(&self, least_value: bool) -> i64 {
+        // let z_divs = [ 1,  1,  1,  26,  1, 26,  1,  1,  1, 26, 26,  26,  26,  26];
+        // let x_incs = [10, 13, 13, -11, 11, -4, 12, 12, 15, -2, -5, -11, -13, -10];
+        // let y_incs = [13, 10,  3,   1,  9,  3,  5,  1,  0, 13,  7,  15,  12,   8];
+
+        let mut z_divs = [0i64; 14];
+        let mut x_incs = [0i64; 14];
+        let mut y_incs = [0i64; 14];
+
+        for i in 0..14 {
+            let start = i * 18;
+
+            let Instruction(_, _, z_div) = self.instructions[start + 4];
+
+            match z_div {
+                Operand::Value(v) => z_divs[i] = v,
+                _ => unreachable!(),
+            }
+
+            let Instruction(_, _, x_inc) = self.instructions[start + 5];
+
+            match x_inc {
+                Operand::Value(v) => x_incs[i] = v,
+                _ => unreachable!(),
+            }
+
+            let Instruction(_, _, y_inc) = self.instructions[start + 15];
+
+            match y_inc {
+                Operand::Value(v) => y_incs[i] = v,
+                _ => unreachable!(),
+            }
+        }
+
+        let mut zs: HashSet<i64> = HashSet::new();
+        let mut result: HashMap<i64, Vec<i64>> = HashMap::new();
+
+        zs.insert(0);
+
+        for ops_idx in (0..14).rev() {
+            let mut updated_zs = HashSet::new();
+
+            for _w in 1..=9 {
+                let w = 
+                    if !least_value {
+                        _w
+                    } else {
+                        10 - _w
+                    };
+
+                for z in zs.iter() {
+                    let generated_zs =
+                        go_backward(*z, w, x_incs[ops_idx], y_incs[ops_idx], z_divs[ops_idx]);
+
+                    // println!("w: {}, len {}", w, result.len());
+                    for z0 in generated_zs {
+                        updated_zs.insert(z0);
+
+                        if let Some(existing_solutions) = result.get(z) {
+                            let mut new_existing_solutions = existing_solutions.clone();
+                            new_existing_solutions.insert(0, w);
+
+                            result.insert(z0, new_existing_solutions);
+                        } else {
+                            result.insert(z0, vec![w]);
+                        }
+                    }
+                }
+            }
+
+            zs = updated_zs;
+        }
+
+        // for (k, v) in result.iter() {
+        //     println!("{}: {:?}", k, v);
+        // }
+
+        if let Some(r) = result.get(&0) {
+            let solution = r.iter().fold(0, |acc, value| acc * 10 + value);
+
+            return solution;
+        }
+
+        return -1;
+    }
+
+    // Runs the whole program from the current registers, each `inp` reads
+    // the next value of `input`
+    pub fn run(&mut self, input: &[i64]) -> Result<(), AluError> {
+        let mut input = input.iter();
+
+        for pc in 0..self.instructions.len() {
+            let instruction = self.instructions[pc];
+            let Instruction(op, var1_name, var2) = instruction;
+
+            let error = |kind| AluError { pc, instruction, kind };
+
+            if op == Operator::Inp {
+                let value = *input.next().ok_or_else(|| error(AluErrorKind::MissingInput))?;
+
+                self.load(var1_name, value);
+                continue;
+            }
+
+            let a = self.get(var1_name);
+            let b =
+                match var2 {
+                    Operand::Value(value) => value,
+                    Operand::Var(var2_name) => self.get(var2_name),
+                    Operand::Empty => unreachable!(),
+                };
+
+            let result = match op {
+                Operator::Add => a.checked_add(b).ok_or_else(|| error(AluErrorKind::Overflow))?,
+                Operator::Mul => a.checked_mul(b).ok_or_else(|| error(AluErrorKind::Overflow))?,
+                Operator::Div => {
+                    if b == 0 {
+                        return Err(error(AluErrorKind::DivisionByZero));
+                    }
+
+                    // Truncated toward zero, as the puzzle wants
+                    a.checked_div(b).ok_or_else(|| error(AluErrorKind::Overflow))?
+                },
+                Operator::Mod => {
+                    if a < 0 || b <= 0 {
+                        return Err(error(AluErrorKind::InvalidModulo));
+                    }
+
+                    a % b
+                },
+                Operator::Eql => (a == b) as i64,
+                Operator::Inp => unreachable!(),
+            };
+
+            self.load(var1_name, result);
+        }
+
+        Ok(())
+    }
+}
+
+// Digits of a model number, the most significant first
+fn digits(mut number: i64) -> Vec<i64> {
+    let mut digits = vec![];
+
+    while number > 0 {
+        digits.push(number % 10);
+        number /= 10;
+    }
+
+    digits.reverse();
+    digits
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Arg {
+    Reg(usize),
+    Imm(i64),
+}
+
+// Instruction left once the constants are folded, `inp` has the index of
+// the input it reads as its first argument
+#[derive(Debug, Copy, Clone)]
+struct Op {
+    op: Operator,
+    dst: usize,
+    a: Arg,
+    b: Arg,
+}
+
+impl Op {
+    // Dead code can only be dropped when it can't stop the program
+    fn can_fail(&self) -> bool {
+        match self.op {
+            Operator::Div => !matches!(self.b, Arg::Imm(v) if v != 0),
+            Operator::Mod => true,
+            _ => false,
+        }
+    }
+}
+
+fn register(name: char) -> usize {
+    "wxyz".find(name).unwrap()
+}
+
+type Step = Box<dyn Fn(&mut [i64; 4], &[i64]) -> Option<()>>;
+
+fn binary(dst: usize, a: Arg, b: Arg, f: impl Fn(i64, i64) -> Option<i64> + 'static) -> Step {
+    match (a, b) {
+        (Arg::Reg(a), Arg::Reg(b)) => Box::new(move |r, _| { r[dst] = f(r[a], r[b])?; Some(()) }),
+        (Arg::Reg(a), Arg::Imm(b)) => Box::new(move |r, _| { r[dst] = f(r[a], b)?; Some(()) }),
+        (Arg::Imm(a), Arg::Reg(b)) => Box::new(move |r, _| { r[dst] = f(a, r[b])?; Some(()) }),
+        (Arg::Imm(a), Arg::Imm(b)) => Box::new(move |r, _| { r[dst] = f(a, b)?; Some(()) }),
+    }
+}
+
+// Same results as the ALU except for overflows, which wrap around
+fn step(Op { op, dst, a, b }: Op) -> Step {
+    match op {
+        Operator::Inp => {
+            let Arg::Imm(n) = a else { unreachable!() };
+            let n = n as usize;
+
+            Box::new(move |r, input| { r[dst] = input[n]; Some(()) })
+        },
+        Operator::Add => binary(dst, a, b, |a, b| Some(a.wrapping_add(b))),
+        Operator::Mul => binary(dst, a, b, |a, b| Some(a.wrapping_mul(b))),
+        Operator::Div => binary(dst, a, b, |a, b| if b == 0 { None } else { Some(a.wrapping_div(b)) }),
+        Operator::Mod => binary(dst, a, b, |a, b| if a < 0 || b <= 0 { None } else { Some(a % b) }),
+        Operator::Eql => binary(dst, a, b, |a, b| Some((a == b) as i64)),
+    }
+}
+
+// A program turned into a chain of closures, one for each instruction left
+// once the constants are folded and the writes nobody reads are dropped.
+// Only z is kept, to check model numbers much faster than the ALU does.
+pub struct Compiled {
+    steps: Vec<Step>,
+    inputs: usize,
+
+    // (step, input) for the steps reading an input
+    reads: Vec<(usize, usize)>,
+
+    // z when it doesn't depend on the inputs
+    z: Option<i64>,
+}
+
+impl Compiled {
+    pub fn new(instructions: &[Instruction]) -> Compiled {
+        let mut known = [Some(0); 4];
+        let mut ops = vec![];
+        let mut inputs = 0;
+
+        for &Instruction(op, var1, var2) in instructions {
+            let dst = register(var1);
+
+            if op == Operator::Inp {
+                ops.push(Op { op, dst, a: Arg::Imm(inputs as i64), b: Arg::Imm(0) });
+                known[dst] = None;
+                inputs += 1;
+                continue;
+            }
+
+            let arg = |r: usize| known[r].map_or(Arg::Reg(r), Arg::Imm);
+
+            let a = arg(dst);
+            let b = match var2 {
+                Operand::Value(v) => Arg::Imm(v),
+                Operand::Var(name) => arg(register(name)),
+                Operand::Empty => unreachable!(),
+            };
+
+            let value = match (op, a, b) {
+                (Operator::Add, Arg::Imm(a), Arg::Imm(b)) => Some(a.wrapping_add(b)),
+                (Operator::Mul, Arg::Imm(a), Arg::Imm(b)) => Some(a.wrapping_mul(b)),
+                (Operator::Div, Arg::Imm(a), Arg::Imm(b)) if b != 0 => Some(a.wrapping_div(b)),
+                (Operator::Mod, Arg::Imm(a), Arg::Imm(b)) if a >= 0 && b > 0 => Some(a % b),
+                (Operator::Eql, Arg::Imm(a), Arg::Imm(b)) => Some((a == b) as i64),
+                (Operator::Mul, _, Arg::Imm(0)) | (Operator::Mul, Arg::Imm(0), _) => Some(0),
+                (Operator::Eql, Arg::Reg(a), Arg::Reg(b)) if a == b => Some(1),
+                _ => None,
+            };
+
+            if value.is_some() {
+                known[dst] = value;
+                continue;
+            }
+
+            // Instructions leaving the register as it was
+            match (op, b) {
+                (Operator::Add, Arg::Imm(0)) | (Operator::Mul, Arg::Imm(1)) | (Operator::Div, Arg::Imm(1)) => {
+                    if a == Arg::Reg(dst) {
+                        continue;
+                    }
+                },
+                _ => {},
+            }
+
+            ops.push(Op { op, dst, a, b });
+            known[dst] = None;
+        }
+
+        let z = known[register('z')];
+
+        // Backward through the program, only z is wanted at the end
+        let mut live = [false; 4];
+        live[register('z')] = z.is_none();
+
+        let mut kept = vec![];
+
+        for op in ops.into_iter().rev() {
+            if !live[op.dst] && !op.can_fail() {
+                continue;
+            }
+
+            live[op.dst] = false;
+
+            for arg in [op.a, op.b] {
+                if let Arg::Reg(r) = arg {
+                    live[r] = true;
+                }
+            }
+
+            kept.push(op);
+        }
+
+        kept.reverse();
+
+        let reads = kept
+            .iter()
+            .enumerate()
+            .filter_map(|(i, op)| match (op.op, op.a) {
+                (Operator::Inp, Arg::Imm(n)) => Some((i, n as usize)),
+                _ => None,
+            })
+            .collect();
+
+        Compiled {
+            steps: kept.into_iter().map(step).collect(),
+            inputs,
+            reads,
+            z,
+        }
+    }
+
+    // Instructions left to run
+    pub fn steps(&self) -> usize {
+        self.steps.len()
+    }
+
+    // z at the end, None when the ALU would stop on an error
+    pub fn run(&self, input: &[i64]) -> Option<i64> {
+        if input.len() < self.inputs {
+            return None;
+        }
+
+        let mut registers = [0; 4];
+
+        for step in &self.steps {
+            step(&mut registers, input)?;
+        }
+
+        Some(self.z.unwrap_or(registers[register('z')]))
+    }
+
+    pub fn accepts(&self, input: &[i64]) -> bool {
+        self.run(input) == Some(0)
+    }
+}
+
+// Runs a compiled program on inputs sharing their first values with the
+// previous ones, from the registers saved when the first different value
+// was read. Counting model numbers down mostly changes the last digit.
+pub struct Checker<'a> {
+    compiled: &'a Compiled,
+    input: Vec<i64>,
+
+    // Registers before each read of the previous run
+    saved: Vec<[i64; 4]>,
+
+    // Steps done by the previous run, all of them unless one failed
+    reached: usize,
+    result: Option<i64>,
+    ran: bool,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(compiled: &'a Compiled) -> Checker<'a> {
+        Checker {
+            compiled,
+            input: vec![],
+            saved: vec![[0; 4]; compiled.reads.len()],
+            reached: 0,
+            result: None,
+            ran: false,
+        }
+    }
+
+    // Same as Compiled::run
+    pub fn run(&mut self, input: &[i64]) -> Option<i64> {
+        let compiled = self.compiled;
+
+        if input.len() < compiled.inputs {
+            return None;
+        }
+
+        let changed = compiled.reads.iter().position(|&(_, n)| self.input.get(n) != Some(&input[n]));
+
+        self.input.clear();
+        self.input.extend_from_slice(input);
+
+        let (start, mut read, mut registers) = match changed {
+            _ if !self.ran => (0, 0, [0; 4]),
+            None => return self.result,
+            Some(read) => (compiled.reads[read].0, read, self.saved[read]),
+        };
+
+        // The previous run stopped before, on the same values
+        if start > self.reached {
+            return None;
+        }
+
+        self.ran = true;
+        self.result = None;
+
+        for pc in start..compiled.steps.len() {
+            if read < compiled.reads.len() && compiled.reads[read].0 == pc {
+                self.saved[read] = registers;
+                read += 1;
+            }
+
+            if compiled.steps[pc](&mut registers, input).is_none() {
+                self.reached = pc;
+                return None;
+            }
+        }
+
+        self.reached = compiled.steps.len();
+        self.result = Some(compiled.z.unwrap_or(registers[register('z')]));
+        self.result
+    }
+
+    pub fn accepts(&mut self, input: &[i64]) -> bool {
+        self.run(input) == Some(0)
+    }
+}
+
+/*
+ * x(n) = (z(n-1) % 26) + x_inc
+ * z(n) = 26 * (z(n-1) // z_div) + w + y_inc, if x(n) != w
+ * z(n) = z(n-1) // divisor                 , if x(n) == w
+ */
+fn go_backward(zf: i64, w: i64, x_inc: i64, y_inc: i64, z_div: i64) -> Vec<i64> {
+    let mut possible_zs = vec![];
+
+    // k = zf - w - y_inc = 26 * (zf // z_div)
+    let k = zf - w - y_inc;
+
+    // testing if k is a multiple of 26
+    if k % 26 == 0 {
+        let t = k / 26;
+        let zi = t * z_div;
+
+        possible_zs.push(zi);
+    }
+
+    // After an integer division, the remainder is lost
+    // Testing if w - x_inc can be the remainder of zi % 26,
+    // where can be derived from x(n) = (z(n-1) % 26) + x_inc
+    // zi % 26 = w - x_inc, when x(n) = w
+    if 0 <= (w - x_inc) && (w - x_inc) < 26 {
+        let zi = zf * 26 + w - x_inc;
+        possible_zs.push(zi);
+    }
+
+    return possible_zs;
+}
+
+
+fn parse_register(line: &parse::Line, term: &str) -> Result<char, ParseError> {
+    match term {
+        "w" | "x" | "y" | "z" => Ok(term.chars().nth(0).unwrap()),
+        _ => Err(line.error(term, "expecting a register in w, x, y, z")),
+    }
+}
+
+fn parse_instruction(line: &parse::Line) -> Result<Instruction, ParseError> {
+    let terms: Vec<&str> = line.text
+        .split(' ')
+        .collect();
+
+    let operator = match terms[0] {
+        "inp" => Operator::Inp,
+        "add" => Operator::Add,
+        "mul" => Operator::Mul,
+        "div" => Operator::Div,
+        "mod" => Operator::Mod,
+        "eql" => Operator::Eql,
+        _ => return Err(line.error(terms[0], "unknown instruction")),
+    };
+
+    let term_count = if operator == Operator::Inp { 2 } else { 3 };
+
+    if terms.len() != term_count {
+        let reason = format!("expecting {} operands", term_count - 1);
+        return Err(line.error(line.text, &reason));
+    }
+
+    let var1: char = parse_register(line, terms[1])?;
+
+    if operator == Operator::Inp {
+        return Ok(Instruction(Operator::Inp, var1, Operand::Empty));
+    }
+
+    let var2 = 
+        if let Ok(value) = i64::from_str_radix(terms[2], 10) {
+            Operand::Value(value)
+        } else {
+            Operand::Var(parse_register(line, terms[2])?)
+        };
+
+    Ok(Instruction(operator, var1, var2))
+}
+
+// Any ALU program, one instruction per line
+pub fn assemble(content: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(content)
+        .map(|line| parse_instruction(&line))
+        .collect()
+}
+
+// Source text `assemble` reads back into the same instructions
+pub fn disassemble(instructions: &[Instruction]) -> String {
+    instructions
+        .iter()
+        .map(|i| format!("{}\n", i))
+        .collect()
+}
+
+// The solver reads the constants of the 14 digit blocks of MONAD, so the
+// program must have that shape
+fn read_program(content: &str) -> Result<Vec<Instruction>, ParseError> {
+    let lines: Vec<parse::Line> = parse::lines(content).collect();
+    let instructions = assemble(content)?;
+
+    if instructions.len() != 14 * 18 {
+        return Err(ParseError::end_of_input(content, "MONAD has 14 blocks of 18 instructions"));
+    }
+
+    for i in 0..14 {
+        for offset in [4, 5, 15] {
+            let pos = i * 18 + offset;
+
+            if let Instruction(_, _, Operand::Value(_)) = instructions[pos] {
+                continue;
+            }
+
+            return Err(lines[pos].error(lines[pos].text, "expecting a MONAD constant"));
+        }
+    }
+
+    Ok(instructions)
+}
+
+// The largest model number, or the least one, found from the MONAD
+// constants and then checked by the whole program
+fn solve(content: &str, least_value: bool, logger: &Logger) -> Result<Answer, ParseError> {
+    let instructions: Vec<Instruction> = read_program(content)?;
+    let compiled = Compiled::new(&instructions);
+
+    let mut alu = ALU::new(instructions);
+    let input = alu.solve(least_value);
+
+    if input == -1 {
+        return Err(ParseError::end_of_input(content, "no model number is accepted"));
+    }
+
+    // solve() only reads the MONAD constants, the whole program checks it
+    if !compiled.accepts(&digits(input)) {
+        return Err(ParseError::end_of_input(content, &format!("{} is rejected by the program", input)));
+    }
+
+    alu.reset();
+
+    match alu.run(&digits(input)) {
+        Ok(()) => log!(logger, "input: {}", input),
+        Err(e) => log!(logger, "input: {}, {}", input, e),
+    }
+
+    log!(logger, "x: {}, y: {}, z: {}, w: {}", alu.x, alu.y, alu.z, alu.w);
+
+    Ok(Answer::from(input))
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    solve(content, false, logger)
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    solve(content, true, logger)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_program(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str, input: &[i64]) -> Result<ALU, AluError> {
+        let mut alu = ALU::new(assemble(program).unwrap());
+
+        alu.run(input)?;
+        Ok(alu)
+    }
+
+    #[test]
+    fn examples() {
+        let negate = include_str!("../inputs/day24-example.txt");
+        assert_eq!(run(negate, &[7]).unwrap().x, -7);
+
+        // z is 1 when the second number is three times the first
+        let triple = include_str!("../inputs/day24-example2.txt");
+        assert_eq!(run(triple, &[4, 12]).unwrap().z, 1);
+        assert_eq!(run(triple, &[4, 11]).unwrap().z, 0);
+
+        // The bits of the number, the lowest in z
+        let binary = include_str!("../inputs/day24-example3.txt");
+        let alu = run(binary, &[13]).unwrap();
+        assert_eq!([alu.w, alu.x, alu.y, alu.z], [1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |program: &str, input: &[i64]| {
+            let e = run(program, input).err().unwrap();
+            (e.pc, e.kind)
+        };
+
+        assert_eq!(error("inp x\ndiv x y", &[1]), (1, AluErrorKind::DivisionByZero));
+        assert_eq!(error("inp x\nmod x 2", &[-3]), (1, AluErrorKind::InvalidModulo));
+        assert_eq!(error("inp x\nmod x 0", &[3]), (1, AluErrorKind::InvalidModulo));
+        assert_eq!(error("inp x\ninp y", &[3]), (1, AluErrorKind::MissingInput));
+        assert_eq!(error("inp x\nmul x x\nmul x x", &[1 << 40]), (1, AluErrorKind::Overflow));
+
+        let e = run("inp w\nadd z 1\ndiv w z\nadd z -1\ndiv w z", &[5]).err().unwrap();
+        assert_eq!(e.to_string(), "instruction 5 (div w z): division by zero");
+
+        // Division truncates toward zero
+        assert_eq!(run("inp x\ndiv x 2", &[-7]).unwrap().x, -3);
+    }
+
+    #[test]
+    fn disassembly_round_trip() {
+        let monad = include_str!("../inputs/day24.txt");
+        let instructions = assemble(monad).unwrap();
+
+        assert_eq!(disassemble(&instructions), monad);
+
+        let program = "inp w\nadd x -12\nmul y z\ndiv z 26\nmod w 2\neql x w\n";
+        assert_eq!(disassemble(&assemble(program).unwrap()), program);
+
+        assert_eq!(Instruction(Operator::Add, 'x', Operand::Value(-1)).to_string(), "add x -1");
+    }
+
+    #[test]
+    fn program_without_model_numbers() {
+        // No digit pops the block pushed before it, the number found from
+        // the constants is rejected by the whole program
+        let monad = include_str!("../inputs/day24.txt").replacen("add x -11", "add x -30", 1);
+        let logger = Logger::silent();
+
+        for part in [part1, part2] {
+            let e = part(&monad, &logger).unwrap_err();
+            assert!(e.to_string().contains("is rejected by the program"), "{}", e);
+        }
+    }
+
+    #[test]
+    fn digits_of_model_numbers() {
+        assert_eq!(digits(13579246899999), [1, 3, 5, 7, 9, 2, 4, 6, 8, 9, 9, 9, 9, 9]);
+    }
+
+    #[test]
+    fn compiled_programs() {
+        let compile = |program: &str| Compiled::new(&assemble(program).unwrap());
+
+        // x and y are constants, z is w + 10
+        let folded = compile("inp w\nmul x 0\nadd x 5\nadd y x\nmul y 2\nadd z w\nadd z y\n");
+        assert_eq!(folded.steps(), 3);
+        assert_eq!(folded.run(&[4]), Some(14));
+
+        // Nothing reads x, but the division by zero stops the program
+        let dead = compile("inp x\nmul x 2\ninp w\nadd z w\n");
+        assert_eq!(dead.steps(), 2);
+        assert_eq!(dead.run(&[1, 2]), Some(2));
+        assert_eq!(dead.run(&[1]), None);
+        assert_eq!(compile("inp w\ndiv w x\nadd z 1\n").run(&[3]), None);
+
+        assert!(compile("inp w\nadd z 1\nadd z -1\n").accepts(&[5]));
+
+        let monad = compile(include_str!("../inputs/day24.txt"));
+        assert!(monad.steps() < 252);
+        assert!(monad.accepts(&digits(69914999975369)));
+        assert!(!monad.accepts(&digits(69914999975368)));
+    }
+
+    #[test]
+    fn compiled_programs_agree_with_the_alu() {
+        let mut seed = 0x2545f4914f6cdd1du64;
+        let mut random = |range: i64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % range as u64) as i64
+        };
+
+        let programs = [
+            (include_str!("../inputs/day24.txt"), 1),
+            (include_str!("../inputs/day24-example.txt"), -50),
+            (include_str!("../inputs/day24-example2.txt"), -50),
+            (include_str!("../inputs/day24-example3.txt"), -50),
+        ];
+
+        for (program, low) in programs {
+            let instructions = assemble(program).unwrap();
+            let compiled = Compiled::new(&instructions);
+            let mut checker = Checker::new(&compiled);
+            let mut alu = ALU::new(instructions);
+
+            let mut input: Vec<i64> = (0..14).map(|_| low + random(10 - low)).collect();
+
+            // Mostly the last values change, as when counting down
+            for _ in 0..2000 {
+                let from = 13 - random(14).min(random(14)) as usize;
+
+                for value in &mut input[from..] {
+                    *value = low + random(10 - low);
+                }
+
+                alu.reset();
+                let expected = alu.run(&input).ok().map(|()| alu.z);
+
+                assert_eq!(compiled.run(&input), expected, "{:?}", input);
+                assert_eq!(checker.run(&input), expected, "{:?}", input);
+            }
+        }
+    }
+}
+
+/*
+z = 10 + w
+z = -(w + 8) * 26 + 10 + w
+
+
+a = [ 1,  1,  1,  26,  1, 26,  1,  1,  1, 26, 26,  26,  26,  26];
+b = [10, 13, 13, -11, 11, -4, 12, 12, 15, -2, -5, -11, -13, -10];
+c = [13, 10,  3,   1,  9,  3,  5,  1,  0, 13,  7,  15,  12,   8];
+
+w = read
+x = z % 26 + b;
+
+if x != w {
+    x = 1;
+} else {
+    x = 0;
+}
+
+z = z / a;
+z = x * (25z + w + c) + z
+
+
+w = read            w = v1
+x = x * 0;          x = 0   // mul x 0
+x = x + z;          z = 0   // add x z
+x = x % 26;         x = 0  // mod x 26
+z = z / 1;          z = 0   // div z 1
+x = x + 10;         x = 10  // add x 10
+x = x == w ? 1 : 0; x = 0   // eql x w
+x = x == 0 ? 1 : 0; x = 1   // eql x 0
+y = y * 0;          y = 0   // mul y 0
+y = y + 25;         y = 25  // add y 25
+y = y * x;          y = 25 // mul y x
+y = y + 1;          y = 26 // add y 1
+z = z * y;          z = 0  // mul z y
+y = y * 0;          y = 0   // mul y 0
+y = y + w           y = v1  // add y w
+y = y + 13;         y = v1 + 13 // add y 13
+y = y * x;          y = v1 + 13 // mul y x
+z = z + y;          z = v1 + 13 // add z y
+
+w = read            w = v2
+x = x * 0;          x = 0          // mul x 0
+x = x + z;          x = v1 + 13;   // add x z
+x = x % 26;         x = 0          // mod x 26
+z = z / 1;          z = v1 + 13;   // div z 1
+x = x + 13;         x = 13         // add x 13
+x = x == w ? 1 : 0; x = 0          // eql x w
+x = x == 0 ? 1 : 0; x = 1          // eql x 0
+y = y * 0;          y = 0;         // mul y 0
+y = y + 25;         y = 25;        // add y 25
+y = y * x;          y = 25;        // mul y x
+y = y + 1;          y = 26;        // add y 1
+z = z * y;          z = (v1+13)*26 // mul z y
+y = y * 0;          y = 0;         // mul y 0
+y = y + w;          y = v2;        // add y w
+y = y + 10;         y = v2 + 10    // add y 10
+y = y * x;          y = v2 + 10    // mul y x
+z = z + y;          z = (v1+13)*26+v2+10 //  add z y
+*/
@@ -0,0 +1,702 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::day24::{self, Instruction, Operand, Operator};
+use crate::log;
+use crate::parse::ParseError;
+use crate::solution::{Answer, Logger, Solution};
+
+// Every input is a digit of the model number
+const DIGITS: (i64, i64) = (1, 9);
+
+#[derive(Debug)]
+enum Node {
+    Const(i64),
+
+    // The input read by the nth `inp`
+    Input(usize),
+
+    Add(Expr, Expr),
+    Mul(Expr, Expr),
+    Div(Expr, Expr),
+    Mod(Expr, Expr),
+    Eql(Expr, Expr),
+}
+
+#[derive(Debug)]
+struct Term {
+    node: Node,
+
+    // Bounds of the values the expression can take
+    lo: i64,
+    hi: i64,
+}
+
+// Value of a register as an expression of the inputs, shared between the
+// registers and the expressions built on it
+#[derive(Debug, Clone)]
+pub struct Expr(Rc<Term>);
+
+type Range = (i64, i64);
+
+fn clamp(v: i128) -> i64 {
+    v.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
+fn corners(a: Range, b: Range, f: impl Fn(i128, i128) -> i128) -> Range {
+    let values = [
+        f(a.0 as i128, b.0 as i128),
+        f(a.0 as i128, b.1 as i128),
+        f(a.1 as i128, b.0 as i128),
+        f(a.1 as i128, b.1 as i128),
+    ];
+
+    (clamp(*values.iter().min().unwrap()), clamp(*values.iter().max().unwrap()))
+}
+
+// Bounds of an operation on values within `a` and `b`
+fn combine(node: &Node, a: Range, b: Range) -> Range {
+    match node {
+        Node::Const(_) | Node::Input(_) => unreachable!(),
+        Node::Add(..) => corners(a, b, |x, y| x + y),
+        Node::Mul(..) => corners(a, b, |x, y| x * y),
+        Node::Div(..) => {
+            if b.0 > 0 || b.1 < 0 {
+                corners(a, b, |x, y| x / y)
+            } else {
+                // Only a divisor of 0 is left out, which the ALU refuses
+                let m = a.0.unsigned_abs().max(a.1.unsigned_abs()) as i128;
+                (clamp(-m), clamp(m))
+            }
+        },
+        Node::Mod(..) => {
+            // The ALU refuses negative numbers and divisors under 1
+            if a.0 >= 0 && a.1 < b.0 {
+                a
+            } else {
+                (0, b.1.saturating_sub(1).max(0).min(a.1.max(0)))
+            }
+        },
+        Node::Eql(..) => {
+            if a.0 == a.1 && b.0 == b.1 {
+                let v = (a.0 == b.0) as i64;
+                (v, v)
+            } else if a.1 < b.0 || b.1 < a.0 {
+                (0, 0)
+            } else {
+                (0, 1)
+            }
+        },
+    }
+}
+
+impl Expr {
+    fn new(node: Node) -> Expr {
+        let (lo, hi) = match &node {
+            Node::Const(v) => (*v, *v),
+            Node::Input(_) => DIGITS,
+            Node::Add(a, b) | Node::Mul(a, b) | Node::Div(a, b) | Node::Mod(a, b) | Node::Eql(a, b) => {
+                combine(&node, a.range(), b.range())
+            },
+        };
+
+        Expr(Rc::new(Term { node, lo, hi }))
+    }
+
+    pub fn constant(v: i64) -> Expr {
+        Expr::new(Node::Const(v))
+    }
+
+    pub fn input(n: usize) -> Expr {
+        Expr::new(Node::Input(n))
+    }
+
+    fn range(&self) -> Range {
+        (self.0.lo, self.0.hi)
+    }
+
+    // The value when there's only one
+    pub fn value(&self) -> Option<i64> {
+        if self.0.lo == self.0.hi { Some(self.0.lo) } else { None }
+    }
+
+    // (p, c) for p * c with a constant c
+    fn scaled(&self) -> Option<(&Expr, i64)> {
+        match &self.0.node {
+            Node::Mul(p, c) => Some((p, c.value()?)),
+            _ => None,
+        }
+    }
+
+    // Constants go right, (e + c1) + c2 is e + (c1 + c2)
+    pub fn add(&self, other: &Expr) -> Expr {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) => return Expr::constant(a.wrapping_add(b)),
+            (Some(0), None) => return other.clone(),
+            (None, Some(0)) => return self.clone(),
+            (Some(_), None) => return other.add(self),
+            _ => {},
+        }
+
+        if let (Node::Add(e, c1), Some(c2)) = (&self.0.node, other.value()) {
+            if let Some(c1) = c1.value() {
+                return e.add(&Expr::constant(c1.wrapping_add(c2)));
+            }
+        }
+
+        Expr::new(Node::Add(self.clone(), other.clone()))
+    }
+
+    pub fn mul(&self, other: &Expr) -> Expr {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) => return Expr::constant(a.wrapping_mul(b)),
+            (Some(0), _) | (_, Some(0)) => return Expr::constant(0),
+            (Some(1), None) => return other.clone(),
+            (None, Some(1)) => return self.clone(),
+            (Some(_), None) => return other.mul(self),
+            _ => {},
+        }
+
+        if let (Some((e, c1)), Some(c2)) = (self.scaled(), other.value()) {
+            return e.mul(&Expr::constant(c1.wrapping_mul(c2)));
+        }
+
+        Expr::new(Node::Mul(self.clone(), other.clone()))
+    }
+
+    pub fn sub(&self, other: &Expr) -> Expr {
+        self.add(&other.mul(&Expr::constant(-1)))
+    }
+
+    pub fn div(&self, other: &Expr) -> Expr {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) if b != 0 => return Expr::constant(a / b),
+            (_, Some(1)) => return self.clone(),
+            _ => {},
+        }
+
+        if let Some(d) = other.value().filter(|&d| d > 0) {
+            let (lo, hi) = self.range();
+
+            if lo >= 0 && hi < d {
+                return Expr::constant(0);
+            }
+
+            // p * d / d, and (p * d + q) / d with q a remainder of d
+            if let Some((p, c)) = self.scaled() {
+                if c == d {
+                    return p.clone();
+                }
+            }
+
+            if let Node::Add(x, q) = &self.0.node {
+                if let Some((p, c)) = x.scaled() {
+                    if c == d && p.0.lo >= 0 && q.0.lo >= 0 && q.0.hi < d {
+                        return p.clone();
+                    }
+                }
+            }
+        }
+
+        Expr::new(Node::Div(self.clone(), other.clone()))
+    }
+
+    pub fn rem(&self, other: &Expr) -> Expr {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) if a >= 0 && b > 0 => return Expr::constant(a % b),
+            _ => {},
+        }
+
+        if let Some(m) = other.value().filter(|&m| m > 0) {
+            let (lo, hi) = self.range();
+
+            if lo >= 0 && hi < m {
+                return self.clone();
+            }
+
+            // Multiples of m vanish, from p * c and from p * c + q
+            let multiple = |e: &Expr| e.scaled().is_some_and(|(p, c)| c % m == 0 && p.0.lo >= 0);
+
+            if multiple(self) {
+                return Expr::constant(0);
+            }
+
+            if let Node::Add(x, q) = &self.0.node {
+                if multiple(x) && q.0.lo >= 0 {
+                    return q.rem(other);
+                }
+            }
+        }
+
+        Expr::new(Node::Mod(self.clone(), other.clone()))
+    }
+
+    pub fn eql(&self, other: &Expr) -> Expr {
+        if Rc::ptr_eq(&self.0, &other.0) {
+            return Expr::constant(1);
+        }
+
+        let node = Node::Eql(self.clone(), other.clone());
+
+        match combine(&node, self.range(), other.range()) {
+            (v, w) if v == w => Expr::constant(v),
+            _ => Expr::new(node),
+        }
+    }
+
+    // Bounds once some inputs are known, `memo` keeps the subexpressions
+    // shared by several others
+    fn range_with(&self, inputs: &[Option<i64>], memo: &mut HashMap<*const Term, Range>) -> Range {
+        let key = Rc::as_ptr(&self.0);
+
+        if let Some(&range) = memo.get(&key) {
+            return range;
+        }
+
+        let range = match &self.0.node {
+            Node::Const(v) => (*v, *v),
+            Node::Input(n) => inputs.get(*n).copied().flatten().map_or(DIGITS, |v| (v, v)),
+            Node::Add(a, b) | Node::Mul(a, b) | Node::Div(a, b) | Node::Mod(a, b) | Node::Eql(a, b) => {
+                let (a, b) = (a.range_with(inputs, memo), b.range_with(inputs, memo));
+
+                match (&self.0.node, a.0 == a.1 && b.0 == b.1) {
+                    // Exact values, with the semantics of the ALU
+                    (Node::Div(..), true) if b.0 != 0 => (a.0 / b.0, a.0 / b.0),
+                    (Node::Mod(..), true) if b.0 > 0 && a.0 >= 0 => (a.0 % b.0, a.0 % b.0),
+                    (node, _) => combine(node, a, b),
+                }
+            },
+        };
+
+        memo.insert(key, range);
+        range
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0.node {
+            Node::Const(v) => write!(f, "{}", v),
+            Node::Input(n) => write!(f, "d{}", n),
+            Node::Add(a, b) => match (b.value(), b.scaled()) {
+                (Some(v), _) if v < 0 => write!(f, "({} - {})", a, v.unsigned_abs()),
+                (_, Some((e, -1))) => write!(f, "({} - {})", a, e),
+                _ => write!(f, "({} + {})", a, b),
+            },
+            Node::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Node::Div(a, b) => write!(f, "({} / {})", a, b),
+            Node::Mod(a, b) => write!(f, "({} % {})", a, b),
+            Node::Eql(a, b) => write!(f, "({} == {})", a, b),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Zero,
+    NonZero,
+    NonNegative,
+    Positive,
+}
+
+impl Condition {
+    fn holds(self, v: i64) -> bool {
+        match self {
+            Condition::Zero => v == 0,
+            Condition::NonZero => v != 0,
+            Condition::NonNegative => v >= 0,
+            Condition::Positive => v > 0,
+        }
+    }
+
+    // Some(true) when every value of the range meets the condition,
+    // Some(false) when none does
+    fn on_range(self, (lo, hi): Range) -> Option<bool> {
+        let all = match self {
+            Condition::Zero => lo == 0 && hi == 0,
+            Condition::NonZero => lo > 0 || hi < 0,
+            Condition::NonNegative => lo >= 0,
+            Condition::Positive => lo > 0,
+        };
+
+        let none = match self {
+            Condition::Zero => lo > 0 || hi < 0,
+            Condition::NonZero => lo == 0 && hi == 0,
+            Condition::NonNegative => hi < 0,
+            Condition::Positive => hi <= 0,
+        };
+
+        if all {
+            Some(true)
+        } else if none {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Constraint {
+    pub expr: Expr,
+    pub condition: Condition,
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let condition = match self.condition {
+            Condition::Zero => "== 0",
+            Condition::NonZero => "!= 0",
+            Condition::NonNegative => ">= 0",
+            Condition::Positive => "> 0",
+        };
+
+        write!(f, "{} {}", self.expr, condition)
+    }
+}
+
+// One way through a program, the comparisons the ranges couldn't decide
+// made one way or the other. The program accepts the inputs meeting all the
+// constraints of one of its paths.
+#[derive(Debug, Clone)]
+pub struct Path {
+    pub inputs: usize,
+    pub constraints: Vec<Constraint>,
+}
+
+#[derive(Clone)]
+struct State {
+    registers: [Expr; 4],
+    inputs: usize,
+    constraints: Vec<Constraint>,
+}
+
+impl State {
+    fn register(&self, name: char) -> &Expr {
+        &self.registers["wxyz".find(name).unwrap()]
+    }
+
+    fn set(&mut self, name: char, value: Expr) {
+        self.registers["wxyz".find(name).unwrap()] = value;
+    }
+
+    // False when the constraint can't be met, the path is dead
+    fn constrain(&mut self, expr: Expr, condition: Condition) -> bool {
+        match condition.on_range(expr.range()) {
+            Some(true) => true,
+            Some(false) => false,
+            None => {
+                self.constraints.push(Constraint { expr, condition });
+                true
+            },
+        }
+    }
+}
+
+// Runs `program` on symbolic inputs from `pc`, forking on the comparisons
+// the ranges don't decide. The paths ending with z at 0 go to `paths`.
+fn explore(program: &[Instruction], pc: usize, mut state: State, paths: &mut Vec<Path>) {
+    for pc in pc..program.len() {
+        let Instruction(op, var1, var2) = program[pc];
+
+        if op == Operator::Inp {
+            state.set(var1, Expr::input(state.inputs));
+            state.inputs += 1;
+            continue;
+        }
+
+        let a = state.register(var1).clone();
+        let b = match var2 {
+            Operand::Value(v) => Expr::constant(v),
+            Operand::Var(name) => state.register(name).clone(),
+            Operand::Empty => unreachable!(),
+        };
+
+        let result = match op {
+            Operator::Add => a.add(&b),
+            Operator::Mul => a.mul(&b),
+            Operator::Div => {
+                if !state.constrain(b.clone(), Condition::NonZero) {
+                    return;
+                }
+
+                a.div(&b)
+            },
+            Operator::Mod => {
+                if !state.constrain(a.clone(), Condition::NonNegative) || !state.constrain(b.clone(), Condition::Positive) {
+                    return;
+                }
+
+                a.rem(&b)
+            },
+            Operator::Eql => {
+                let result = a.eql(&b);
+
+                if result.value().is_none() {
+                    let difference = a.sub(&b);
+
+                    let mut equal = state.clone();
+
+                    if equal.constrain(difference.clone(), Condition::Zero) {
+                        equal.set(var1, Expr::constant(1));
+                        explore(program, pc + 1, equal, paths);
+                    }
+
+                    if !state.constrain(difference, Condition::NonZero) {
+                        return;
+                    }
+
+                    Expr::constant(0)
+                } else {
+                    result
+                }
+            },
+            Operator::Inp => unreachable!(),
+        };
+
+        state.set(var1, result);
+    }
+
+    let z = state.register('z').clone();
+
+    if state.constrain(z, Condition::Zero) {
+        paths.push(Path { inputs: state.inputs, constraints: state.constraints });
+    }
+}
+
+// The paths through `program` that end with z at 0
+pub fn accepting_paths(program: &[Instruction]) -> Vec<Path> {
+    let zero = Expr::constant(0);
+
+    let state = State {
+        registers: [zero.clone(), zero.clone(), zero.clone(), zero],
+        inputs: 0,
+        constraints: vec![],
+    };
+
+    let mut paths = vec![];
+    explore(program, 0, state, &mut paths);
+
+    paths
+}
+
+// Digits from the first one, each the largest (or smallest) that can still
+// meet the constraints of the path
+fn search_digits(path: &Path, digits: &mut Vec<Option<i64>>, n: usize, largest: bool) -> bool {
+    // Every constraint either holds or might still hold
+    let mut memo = HashMap::new();
+
+    for c in &path.constraints {
+        match c.condition.on_range(c.expr.range_with(digits, &mut memo)) {
+            Some(false) => return false,
+            Some(true) => {},
+            None if n == path.inputs => {
+                // Known inputs give exact values, this is only reached
+                // through operations the ranges can't follow
+                let (lo, _) = c.expr.range_with(digits, &mut memo);
+
+                if !c.condition.holds(lo) {
+                    return false;
+                }
+            },
+            None => {},
+        }
+    }
+
+    if n == path.inputs {
+        return true;
+    }
+
+    let order: Vec<i64> = if largest {
+        (DIGITS.0..=DIGITS.1).rev().collect()
+    } else {
+        (DIGITS.0..=DIGITS.1).collect()
+    };
+
+    for d in order {
+        digits[n] = Some(d);
+
+        if search_digits(path, digits, n + 1, largest) {
+            return true;
+        }
+    }
+
+    digits[n] = None;
+    false
+}
+
+// Largest (or smallest) input the program accepts, as its digits
+pub fn accepted_input(paths: &[Path], largest: bool) -> Option<Vec<i64>> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let mut digits = vec![None; path.inputs];
+
+            if search_digits(path, &mut digits, 0, largest) {
+                Some(digits.into_iter().map(|d| d.unwrap()).collect::<Vec<i64>>())
+            } else {
+                None
+            }
+        })
+        .reduce(|a, b| if (a > b) == largest { a } else { b })
+}
+
+fn solve(content: &str, largest: bool, logger: &Logger) -> Result<Answer, ParseError> {
+    let program = day24::assemble(content)?;
+
+    if !program.iter().any(|i| i.0 == Operator::Inp) {
+        return Err(ParseError::end_of_input(content, "program reads no input"));
+    }
+
+    let paths = accepting_paths(&program);
+
+    log!(logger, "{} paths accept some input", paths.len());
+
+    if logger.enabled() {
+        for (i, path) in paths.iter().enumerate() {
+            log!(logger, "path {}:", i);
+
+            for c in &path.constraints {
+                log!(logger, "  {}", c);
+            }
+        }
+    }
+
+    match accepted_input(&paths, largest) {
+        Some(digits) => {
+            let number: String = digits.iter().map(|d| d.to_string()).collect();
+
+            // More than 18 digits don't fit in an i64
+            match number.parse::<i64>() {
+                Ok(n) => Ok(Answer::from(n)),
+                Err(_) => Ok(Answer::Str(number)),
+            }
+        },
+        None => Err(ParseError::end_of_input(content, "no model number is accepted")),
+    }
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    solve(content, true, logger)
+}
+
+pub fn part2(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    solve(content, false, logger)
+}
+
+pub struct Day24v2;
+
+impl Solution for Day24v2 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        day24::assemble(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+
+    fn part2(&self, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        Some(part2(content, logger))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepted(program: &str, largest: bool) -> Option<Vec<i64>> {
+        accepted_input(&accepting_paths(&day24::assemble(program).unwrap()), largest)
+    }
+
+    #[test]
+    fn simplification() {
+        let (d0, d1) = (Expr::input(0), Expr::input(1));
+        let base = Expr::constant(26);
+
+        // What MONAD pushes on z comes back out of it
+        let z = d0.add(&Expr::constant(4)).add(&Expr::constant(2));
+        let pushed = z.mul(&base).add(&d1.add(&Expr::constant(7)));
+
+        assert!(Rc::ptr_eq(&pushed.div(&base).0, &z.0));
+        assert_eq!(pushed.rem(&base).to_string(), "(d1 + 7)");
+        assert_eq!(z.to_string(), "(d0 + 6)");
+
+        assert_eq!(d0.add(&Expr::constant(10)).eql(&d1).value(), Some(0));
+        assert_eq!(d0.eql(&d0).value(), Some(1));
+        assert_eq!(d0.eql(&d1).value(), None);
+        assert_eq!(d0.mul(&Expr::constant(0)).value(), Some(0));
+        assert_eq!(d0.div(&Expr::constant(10)).value(), Some(0));
+        assert_eq!(d0.sub(&d1).to_string(), "(d0 - d1)");
+    }
+
+    #[test]
+    fn examples() {
+        let negate = include_str!("../inputs/day24-example.txt");
+        assert_eq!(accepted(negate, true), Some(vec![9]));
+
+        // z is 0 unless the second number is three times the first
+        let triple = include_str!("../inputs/day24-example2.txt");
+        let paths = accepting_paths(&day24::assemble(triple).unwrap());
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].constraints[0].to_string(), "((d0 * 3) - d1) != 0");
+        assert_eq!(accepted_input(&paths, false), Some(vec![1, 1]));
+
+        // The lowest bit is in z
+        let binary = include_str!("../inputs/day24-example3.txt");
+        assert_eq!(accepted(binary, true), Some(vec![8]));
+        assert_eq!(accepted(binary, false), Some(vec![2]));
+    }
+
+    #[test]
+    fn other_programs() {
+        let product = "inp x\ninp y\nmul x y\nadd z x\nadd z -12\n";
+        assert_eq!(accepted(product, true), Some(vec![6, 2]));
+        assert_eq!(accepted(product, false), Some(vec![2, 6]));
+
+        // z has to be 0 at the end and can't be while dividing
+        assert_eq!(accepted("inp w\nadd z w\nmod z 3\ndiv w z\n", true), None);
+        assert_eq!(accepted("inp w\nadd z w\nmod z 3\ndiv w z\nmul z 0\n", true), Some(vec![8]));
+
+        assert_eq!(accepted("inp w\nadd z 1\ndiv z w\n", false), Some(vec![2]));
+        assert_eq!(accepted("inp w\nmul x 0\nmod x 0\n", true), None);
+    }
+
+    #[test]
+    fn long_and_empty_programs() {
+        let logger = Logger::silent();
+
+        let twenty = "inp w\n".repeat(20);
+        assert_eq!(part1(&twenty, &logger), Ok(Answer::Str("9".repeat(20))));
+        assert_eq!(part2(&twenty, &logger), Ok(Answer::Str("1".repeat(20))));
+
+        let e = part1("add z 0\n", &logger).unwrap_err();
+        assert!(e.to_string().contains("program reads no input"), "{}", e);
+
+        // No answer rather than a text the runner would record or submit
+        let e = part1("inp w\nadd z 1\n", &logger).unwrap_err();
+        assert!(e.to_string().contains("no model number is accepted"), "{}", e);
+    }
+
+    #[test]
+    fn agrees_with_solve() {
+        let monad = include_str!("../inputs/day24.txt");
+        let logger = Logger::silent();
+
+        let paths = accepting_paths(&day24::assemble(monad).unwrap());
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].constraints.len(), 7);
+
+        assert_eq!(part1(monad, &logger).unwrap(), day24::part1(monad, &logger).unwrap());
+        assert_eq!(part2(monad, &logger).unwrap(), day24::part2(monad, &logger).unwrap());
+
+        // The inputs found are accepted by the ALU
+        for largest in [true, false] {
+            let mut alu = day24::ALU::new(day24::assemble(monad).unwrap());
+
+            alu.run(&accepted_input(&paths, largest).unwrap()).unwrap();
+            assert_eq!(alu.z, 0);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::grid::Grid;
+use crate::log;
+use crate::parse::ParseError;
+use crate::solution::{Answer, Logger, Solution};
+
+fn read_seafloor(content: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(content, |c| Some(c).filter(|&c| ">v.".contains(c)), "expecting '>', 'v' or '.'")
+}
+
+// Moves the herd of `cucumber` one step in `dir`, the moves are decided on
+// `state` and written to `next`. Returns whether any of them moved.
+fn move_herd(state: &Grid<char>, next: &mut Grid<char>, cucumber: char, dir: (isize, isize)) -> bool {
+    let mut moved = false;
+
+    for (pos, &c) in state.iter() {
+        if c == cucumber {
+            let dest = state.wrapping_offset(pos, dir);
+
+            if state[dest] == '.' {
+                next[pos] = '.';
+                next[dest] = cucumber;
+
+                moved = true;
+            }
+        }
+    }
+
+    moved
+}
+
+pub fn part1(content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+    let mut state = read_seafloor(content)?;
+
+    let mut state_copy = state.clone();
+
+    let mut step_count = 0;
+
+    log!(logger, "{}\n\n", state_copy);
+
+    loop {
+        // Testing movement to the right
+        let moved_east = move_herd(&state, &mut state_copy, '>', (0, 1));
+
+        state.clone_from(&state_copy);
+
+        // Testing movement to south
+        let moved_south = move_herd(&state, &mut state_copy, 'v', (1, 0));
+
+        step_count += 1;
+
+        if !moved_east && !moved_south {
+            break;
+        }
+
+        state.clone_from(&state_copy);
+    }
+
+    log!(logger, "steps: {}", step_count);
+    log!(logger, "{}", state);
+
+    Ok(Answer::from(step_count))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn parse(&self, content: &str) -> Result<(), ParseError> {
+        read_seafloor(content).map(|_| ())
+    }
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError> {
+        part1(content, logger)
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{self, Line, ParseError};
+
+// (row, column)
+pub type Pos = (usize, usize);
+
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1),           (0, 1),
+    (1, -1),  (1, 0),  (1, 1),
+];
+
+// Rectangular grid stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    nrows: usize,
+    ncols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(nrows: usize, ncols: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let data = (0..nrows * ncols)
+            .map(|idx| f((idx / ncols, idx % ncols)))
+            .collect();
+
+        Grid { data, nrows, ncols }
+    }
+
+    // None when the rows differ in length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, |row| row.len());
+
+        if rows.iter().any(|row| row.len() != ncols) {
+            return None;
+        }
+
+        Some(Grid {
+            data: rows.into_iter().flatten().collect(),
+            nrows,
+            ncols,
+        })
+    }
+
+    // One cell per character, `cell` rejects the characters that aren't
+    // cells with `reason`
+    pub fn parse(content: &str, cell: impl Fn(char) -> Option<T>, reason: &str) -> Result<Self, ParseError> {
+        Grid::from_lines(parse::lines(content), content, cell, reason)
+    }
+
+    // Same as `parse` for the grid at the end of an input, the lines left in
+    // `lines`
+    pub fn from_lines<'a>(
+        lines: impl Iterator<Item = Line<'a>>,
+        content: &str,
+        cell: impl Fn(char) -> Option<T>,
+        reason: &str,
+    ) -> Result<Self, ParseError> {
+        let mut data = vec![];
+        let mut nrows = 0;
+        let mut ncols = 0;
+
+        for line in lines {
+            let count = line.text.chars().count();
+
+            if count == 0 || (nrows > 0 && count != ncols) {
+                return Err(line.error(line.text, "rows of the grid differ in length"));
+            }
+
+            for (i, c) in line.text.char_indices() {
+                data.push(cell(c).ok_or_else(|| line.error_at(i, c.len_utf8(), reason))?);
+            }
+
+            nrows += 1;
+            ncols = count;
+        }
+
+        if nrows == 0 {
+            return Err(ParseError::end_of_input(content, "empty grid"));
+        }
+
+        Ok(Grid { data, nrows, ncols })
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    pub fn contains(&self, (i, j): Pos) -> bool {
+        i < self.nrows && j < self.ncols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            self.data.get(pos.0 * self.ncols + pos.1)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            self.data.get_mut(pos.0 * self.ncols + pos.1)
+        } else {
+            None
+        }
+    }
+
+    // Position moved by `delta`, None when it leaves the grid
+    pub fn offset(&self, (i, j): Pos, (di, dj): (isize, isize)) -> Option<Pos> {
+        let pos = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+
+        if self.contains(pos) { Some(pos) } else { None }
+    }
+
+    // Position moved by `delta`, leaving by one side enters by the other
+    pub fn wrapping_offset(&self, (i, j): Pos, (di, dj): (isize, isize)) -> Pos {
+        (
+            (i as isize + di).rem_euclid(self.nrows as isize) as usize,
+            (j as isize + dj).rem_euclid(self.ncols as isize) as usize,
+        )
+    }
+
+    // Up, left, right and down neighbors inside the grid
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS4.iter().filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    // Neighbors inside the grid, diagonals included, row by row
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS8.iter().filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    pub fn wrapping_neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS4.iter().map(move |&delta| self.wrapping_offset(pos, delta))
+    }
+
+    pub fn wrapping_neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS8.iter().map(move |&delta| self.wrapping_offset(pos, delta))
+    }
+
+    // Positions row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let ncols = self.ncols;
+
+        (0..self.data.len()).map(move |idx| (idx / ncols, idx % ncols))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data.iter_mut()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.ncols..(i + 1) * self.ncols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.ncols..(i + 1) * self.ncols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.ncols.max(1))
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(j < self.ncols, "column {} out of a grid of {}", j, self.ncols);
+
+        self.data.iter().skip(j).step_by(self.ncols)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.ncols).map(move |j| self.column(j))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(nrows: usize, ncols: usize, value: T) -> Self {
+        Grid {
+            data: vec![value; nrows * ncols],
+            nrows,
+            ncols,
+        }
+    }
+
+    // Copy surrounded by a border `n` cells wide
+    pub fn padded(&self, n: usize, value: T) -> Self {
+        Grid::from_fn(self.nrows + 2 * n, self.ncols + 2 * n, |(i, j)| {
+            if i < n || j < n || i >= self.nrows + n || j >= self.ncols + n {
+                value.clone()
+            } else {
+                self[(i - n, j - n)].clone()
+            }
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): Pos) -> &T {
+        assert!(i < self.nrows && j < self.ncols, "({}, {}) out of a {}x{} grid", i, j, self.nrows, self.ncols);
+
+        &self.data[i * self.ncols + j]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (i, j): Pos) -> &mut T {
+        assert!(i < self.nrows && j < self.ncols, "({}, {}) out of a {}x{} grid", i, j, self.nrows, self.ncols);
+
+        &mut self.data[i * self.ncols + j]
+    }
+}
+
+// Rows on their own lines, the cells side by side
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(content: &str) -> Grid<u32> {
+        Grid::parse(content, |c| c.to_digit(10), "not a digit").unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = digits("123\n456\n");
+
+        assert_eq!((grid.nrows(), grid.ncols()), (2, 3));
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let err = Grid::parse("12\n3x\n", |c| c.to_digit(10), "not a digit").unwrap_err();
+        assert_eq!((err.line, err.column, err.reason.as_str()), (2, 2, "not a digit"));
+
+        let err = Grid::parse("12\n345\n", |c| c.to_digit(10), "not a digit").unwrap_err();
+        assert_eq!((err.line, err.reason.as_str()), (2, "rows of the grid differ in length"));
+
+        assert!(Grid::parse("", |c| c.to_digit(10), "not a digit").is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = digits("123\n456\n789");
+
+        let corner: Vec<Pos> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, [(0, 1), (1, 0)]);
+
+        let values: Vec<u32> = grid.neighbors8((1, 1)).map(|pos| grid[pos]).collect();
+        assert_eq!(values, [1, 2, 3, 4, 6, 7, 8, 9]);
+
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+
+        let wrapped: Vec<u32> = grid.wrapping_neighbors4((0, 0)).map(|pos| grid[pos]).collect();
+        assert_eq!(wrapped, [7, 3, 2, 4]);
+
+        assert_eq!(grid.wrapping_offset((2, 2), (1, 1)), (0, 0));
+        assert_eq!(grid.offset((2, 2), (1, 0)), None);
+        assert_eq!(grid.wrapping_neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn views() {
+        let mut grid = digits("12\n34\n56");
+
+        assert_eq!(grid.row(1), [3, 4]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), [2, 4, 6]);
+        assert_eq!(grid.columns().count(), 2);
+
+        grid.row_mut(0)[0] = 9;
+        assert_eq!(grid.get((0, 0)), Some(&9));
+        assert_eq!(grid.get((3, 0)), None);
+
+        let sums: Vec<u32> = grid.rows().map(|row| row.iter().sum()).collect();
+        assert_eq!(sums, [11, 7, 11]);
+    }
+
+    #[test]
+    fn padding_and_construction() {
+        let grid = Grid::from_rows(vec![vec!['#']]).unwrap();
+
+        assert_eq!(grid.padded(1, '.').to_string(), "...\n.#.\n...");
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+
+        let grid = Grid::from_fn(2, 2, |(i, j)| i * 2 + j);
+        assert_eq!(grid.map(|v| v * 10).to_string(), "010\n2030");
+        assert_eq!(grid.iter().nth(3), Some(((1, 1), &3)));
+        assert_eq!(Grid::new(2, 3, 0).positions().last(), Some((1, 2)));
+    }
+}
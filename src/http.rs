@@ -0,0 +1,312 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const USER_AGENT: &str = "aoc2021 runner";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpError(pub String);
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+// The few requests the runner makes to the puzzle site. Implementations are
+// swapped in tests for a local server or a fake.
+pub trait HttpClient {
+    // `cookie` is sent as is in the Cookie header, e.g. "session=..."
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, HttpError>;
+
+    // `form` is already encoded, see `form_encode`
+    fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<Response, HttpError>;
+}
+
+// application/x-www-form-urlencoded body of the fields
+pub fn form_encode(fields: &[(&str, &str)]) -> String {
+    let encode = |s: &str| {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+                b' ' => "+".to_string(),
+                b => format!("%{:02X}", b),
+            })
+            .collect::<String>()
+    };
+
+    fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", encode(name), encode(value)))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+// Plain HTTP/1.1 over a socket, no TLS, so only for http:// urls (local
+// servers standing in for the site)
+pub struct TcpClient {
+    pub timeout: Duration,
+}
+
+impl TcpClient {
+    pub fn new() -> Self {
+        TcpClient {
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Default for TcpClient {
+    fn default() -> Self {
+        TcpClient::new()
+    }
+}
+
+// Host with port and the path of an http:// url
+fn split_url(url: &str) -> Result<(String, String), HttpError> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| HttpError(format!("{} is not an http:// url", url)))?;
+
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+
+    let host =
+        if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+
+    Ok((host, path.to_string()))
+}
+
+fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>, HttpError> {
+    let invalid = || HttpError("invalid chunked body".to_string());
+    let mut decoded = vec![];
+
+    loop {
+        let line_end = body.windows(2).position(|w| w == b"\r\n").ok_or_else(invalid)?;
+
+        let size_str = std::str::from_utf8(&body[..line_end]).map_err(|_| invalid())?;
+        let size_str = size_str.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_str, 16).map_err(|_| invalid())?;
+
+        body = &body[line_end + 2..];
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        if body.len() < size {
+            return Err(invalid());
+        }
+
+        decoded.extend_from_slice(&body[..size]);
+        body = body.get(size + 2..).unwrap_or(&[]);
+    }
+}
+
+pub fn parse_response(raw: &[u8]) -> Result<Response, HttpError> {
+    let head_end = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| HttpError("incomplete response".to_string()))?;
+
+    let head = String::from_utf8_lossy(&raw[..head_end]);
+    let mut body = &raw[head_end + 4..];
+
+    let mut lines = head.split("\r\n");
+
+    let status = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| HttpError("invalid status line".to_string()))?;
+
+    let mut chunked = false;
+
+    for line in lines {
+        let (name, value) = match line.split_once(':') {
+            Some((name, value)) => (name.trim().to_ascii_lowercase(), value.trim()),
+            None => continue,
+        };
+
+        if name == "transfer-encoding" && value.eq_ignore_ascii_case("chunked") {
+            chunked = true;
+        } else if name == "content-length" {
+            if let Ok(len) = value.parse::<usize>() {
+                body = &body[..len.min(body.len())];
+            }
+        }
+    }
+
+    let body =
+        if chunked {
+            decode_chunked(body)?
+        } else {
+            body.to_vec()
+        };
+
+    Ok(Response {
+        status,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+impl TcpClient {
+    fn request(&self, method: &str, url: &str, cookie: &str, form: Option<&str>) -> Result<Response, HttpError> {
+        let (host, path) = split_url(url)?;
+        let io_error = |e: std::io::Error| HttpError(format!("{}: {}", host, e));
+
+        let mut stream = TcpStream::connect(&host).map_err(io_error)?;
+
+        stream.set_read_timeout(Some(self.timeout)).map_err(io_error)?;
+        stream.set_write_timeout(Some(self.timeout)).map_err(io_error)?;
+
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: {}\r\nConnection: close\r\n",
+            method, path, host, USER_AGENT, cookie
+        );
+
+        match form {
+            Some(form) => request.push_str(&format!(
+                "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{}",
+                form.len(), form
+            )),
+            None => request.push_str("\r\n"),
+        }
+
+        stream.write_all(request.as_bytes()).map_err(io_error)?;
+
+        let mut raw = vec![];
+        stream.read_to_end(&mut raw).map_err(io_error)?;
+
+        parse_response(&raw)
+    }
+}
+
+impl HttpClient for TcpClient {
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, HttpError> {
+        self.request("GET", url, cookie, None)
+    }
+
+    fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<Response, HttpError> {
+        self.request("POST", url, cookie, Some(form))
+    }
+}
+
+// https:// through the curl binary, the cookie goes through stdin so it
+// doesn't show up in the process list
+pub struct CurlClient;
+
+impl CurlClient {
+    fn request(&self, url: &str, cookie: &str, form: Option<&str>) -> Result<Response, HttpError> {
+        let mut command = Command::new("curl");
+
+        command.args(["--silent", "--show-error", "--header", "@-", "--user-agent", USER_AGENT]);
+
+        // --data-raw makes it a POST of an urlencoded form
+        if let Some(form) = form {
+            command.args(["--data-raw", form]);
+        }
+
+        let mut child = command
+            .args(["--write-out", "\n%{http_code}", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| HttpError(format!("could not run curl: {}", e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = writeln!(stdin, "Cookie: {}", cookie);
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| HttpError(format!("curl failed: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(HttpError(format!("curl failed: {}", String::from_utf8_lossy(&output.stderr).trim())));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .and_then(|(body, code)| Some((body, code.trim().parse::<u16>().ok()?)))
+            .ok_or_else(|| HttpError("unexpected curl output".to_string()))?;
+
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+impl HttpClient for CurlClient {
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, HttpError> {
+        self.request(url, cookie, None)
+    }
+
+    fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<Response, HttpError> {
+        self.request(url, cookie, Some(form))
+    }
+}
+
+// Client able to reach `url`
+pub fn client_for(url: &str) -> Box<dyn HttpClient> {
+    if url.starts_with("http://") {
+        Box::new(TcpClient::new())
+    } else {
+        Box::new(CurlClient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            split_url("http://127.0.0.1:8080/2021/day/1/input"),
+            Ok(("127.0.0.1:8080".to_string(), "/2021/day/1/input".to_string()))
+        );
+        assert_eq!(split_url("http://localhost"), Ok(("localhost:80".to_string(), "/".to_string())));
+        assert!(split_url("https://adventofcode.com").is_err());
+    }
+
+    #[test]
+    fn responses() {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\n1\n2\n3\n";
+        assert_eq!(parse_response(raw), Ok(Response { status: 200, body: "1\n2\n3".to_string() }));
+
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1\n2\n\r\n2\r\n3\n\r\n0\r\n\r\n";
+        assert_eq!(parse_response(raw).unwrap().body, "1\n2\n3\n");
+
+        let raw = b"HTTP/1.1 404 Not Found\r\n\r\nnope";
+        assert_eq!(parse_response(raw).unwrap().status, 404);
+
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+    }
+
+    #[test]
+    fn forms() {
+        assert_eq!(form_encode(&[("level", "1"), ("answer", "1466")]), "level=1&answer=1466");
+        assert_eq!(form_encode(&[("a b", "x&y=é")]), "a+b=x%26y%3D%C3%A9");
+    }
+}
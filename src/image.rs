@@ -0,0 +1,216 @@
+// RGB images written without any dependency: binary PPM, and PNG with the
+// pixels stored uncompressed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: [u8; 3]) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> [u8; 3] {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: [u8; 3]) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+
+        out.extend(self.pixels.iter().flatten());
+        out
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        // Rows of pixels, each with the "None" filter in front
+        let mut raw = Vec::with_capacity(self.height * (1 + 3 * self.width));
+
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+
+        // 8 bits RGB, deflate, no filtering method, no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+        write_chunk(&mut out, b"IHDR", &header);
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut out, b"IEND", &[]);
+
+        out
+    }
+
+    // PNG to embed in HTML or SVG
+    pub fn to_data_uri(&self) -> String {
+        format!("data:image/png;base64,{}", base64(&self.to_png()))
+    }
+}
+
+// Blue for the lowest values through green and yellow to red, `t` in 0..=1
+pub fn heat_color(t: f64) -> [u8; 3] {
+    const STOPS: [[f64; 3]; 5] = [
+        [0.0, 0.0, 255.0],
+        [0.0, 255.0, 255.0],
+        [0.0, 255.0, 0.0],
+        [255.0, 255.0, 0.0],
+        [255.0, 0.0, 0.0],
+    ];
+
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (t.floor() as usize).min(STOPS.len() - 2);
+    let f = t - i as f64;
+
+    let mix = |c: usize| (STOPS[i][c] + (STOPS[i + 1][c] - STOPS[i][c]) * f).round() as u8;
+
+    [mix(0), mix(1), mix(2)]
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+
+    !crc
+}
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+// zlib stream of deflate blocks without compression, at most 65535 bytes each
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(65535).peekable();
+
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn encodings() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(b""), "");
+    }
+
+    #[test]
+    fn ppm() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.set(1, 0, [255, 128, 1]);
+
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x00\x00\x00\xff\x80\x01");
+        assert_eq!(image.get(1, 0), [255, 128, 1]);
+    }
+
+    #[test]
+    fn png_layout() {
+        let image = Image::new(3, 2, [10, 20, 30]);
+        let png = image.to_png();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+
+        // The raw rows are in the single stored block, after the zlib and
+        // block headers
+        let idat = &png[33 + 8..];
+        assert_eq!(&idat[..2], [0x78, 0x01]);
+        assert_eq!(&idat[2..7], [1, 20, 0, !20u8, 0xff]);
+        assert_eq!(&idat[7..11], [0, 10, 20, 30]);
+    }
+
+    #[test]
+    fn heat_colors() {
+        assert_eq!(heat_color(0.0), [0, 0, 255]);
+        assert_eq!(heat_color(0.5), [0, 255, 0]);
+        assert_eq!(heat_color(1.0), [255, 0, 0]);
+        assert_eq!(heat_color(2.0), [255, 0, 0]);
+    }
+}
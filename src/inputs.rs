@@ -0,0 +1,191 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::http::{self, HttpClient, HttpError};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub const YEAR: u32 = 2021;
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, io::Error),
+
+    // The puzzle input is missing and can't be downloaded without a session
+    NoSession(u32, PathBuf),
+
+    // Examples live in the puzzle text, they are never downloaded
+    NoExample(PathBuf),
+
+    Http(String, HttpError),
+    Status(String, u16, String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            InputError::NoSession(day, path) => write!(
+                f,
+                "{} not found, set AOC_SESSION to download the input of day {}",
+                path.display(), day
+            ),
+            InputError::NoExample(path) => write!(f, "{} not found", path.display()),
+            InputError::Http(url, e) => write!(f, "could not download {}: {}", url, e),
+            InputError::Status(url, status, body) => {
+                write!(f, "could not download {}: status {}", url, status)?;
+
+                match body.lines().next() {
+                    Some(line) if !line.trim().is_empty() => write!(f, " ({})", line.trim()),
+                    _ => Ok(()),
+                }
+            },
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// Resolves the days to their files in the inputs directory, which doubles as
+// the download cache:
+//
+//   dayNN.txt           puzzle input, downloaded when missing
+//   dayNN-example.txt   example of the puzzle text
+//   dayNN-exampleK.txt  further examples, when the text has more than one
+pub struct InputManager {
+    dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+    client: Box<dyn HttpClient>,
+}
+
+impl InputManager {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        InputManager {
+            dir: dir.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            client: http::client_for(DEFAULT_BASE_URL),
+        }
+    }
+
+    // inputs/ with the session in AOC_SESSION and AOC_BASE_URL replacing the
+    // site, e.g. by a local server
+    pub fn from_env() -> Self {
+        let mut manager = InputManager::new("inputs");
+
+        if let Ok(url) = env::var("AOC_BASE_URL") {
+            manager = manager.with_base_url(&url);
+        }
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            manager = manager.with_session(&session);
+        }
+
+        manager
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        let session = session.trim();
+
+        self.session = if session.is_empty() { None } else { Some(session.to_string()) };
+        self
+    }
+
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self.client = http::client_for(&self.base_url);
+        self
+    }
+
+    pub fn with_client(mut self, client: Box<dyn HttpClient>) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u32, example: bool) -> PathBuf {
+        if example {
+            self.dir.join(format!("day{:02}-example.txt", day))
+        } else {
+            self.dir.join(format!("day{:02}.txt", day))
+        }
+    }
+
+    pub fn url(&self, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day)
+    }
+
+    pub fn read(&self, day: u32, example: bool) -> Result<String, InputError> {
+        let path = self.path(day, example);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                if example {
+                    Err(InputError::NoExample(path))
+                } else {
+                    self.fetch(day)
+                }
+            },
+            Err(e) => Err(InputError::Io(path, e)),
+        }
+    }
+
+    // Other files of the inputs directory, like the extra examples
+    pub fn read_file(&self, name: &str) -> Result<String, InputError> {
+        let path = self.dir.join(name);
+
+        fs::read_to_string(&path).map_err(|e| InputError::Io(path, e))
+    }
+
+    // Downloads the puzzle input, replacing the cached one
+    pub fn fetch(&self, day: u32) -> Result<String, InputError> {
+        let path = self.path(day, false);
+
+        let session = match &self.session {
+            Some(session) => session,
+            None => return Err(InputError::NoSession(day, path)),
+        };
+
+        let url = self.url(day);
+
+        let response = self.client
+            .get(&url, &format!("session={}", session))
+            .map_err(|e| InputError::Http(url.clone(), e))?;
+
+        if response.status != 200 {
+            return Err(InputError::Status(url, response.status, response.body));
+        }
+
+        // Written aside and renamed, an interrupted download leaves no partial input
+        let partial = path.with_extension("txt.part");
+
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&partial, &response.body))
+            .and_then(|_| fs::rename(&partial, &path))
+            .map_err(|e| InputError::Io(path, e))?;
+
+        Ok(response.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_paths() {
+        let manager = InputManager::new("inputs").with_base_url("http://127.0.0.1:9/");
+
+        assert_eq!(manager.path(1, false), Path::new("inputs/day01.txt"));
+        assert_eq!(manager.path(12, true), Path::new("inputs/day12-example.txt"));
+        assert_eq!(manager.url(7), "http://127.0.0.1:9/2021/day/7/input");
+    }
+}
@@ -0,0 +1,424 @@
+use std::fmt::{self, Write};
+
+use crate::parse::ParseError;
+
+// Just enough JSON for the files the runner writes and reads back (benchmark
+// results, answers ledger). Objects keep the order of their keys.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values.as_slice()),
+            _ => None,
+        }
+    }
+
+    // Indented with two spaces, arrays of scalars stay on one line
+    pub fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out.push('\n');
+        out
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, Value::Array(_) | Value::Object(_))
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize) {
+        let pad = |out: &mut String, n: usize| out.push_str(&"  ".repeat(n));
+
+        match self {
+            Value::Array(values) if !values.is_empty() && !values.iter().all(Value::is_scalar) => {
+                out.push_str("[\n");
+
+                for (i, value) in values.iter().enumerate() {
+                    pad(out, indent + 1);
+                    value.write_pretty(out, indent + 1);
+                    out.push_str(if i + 1 < values.len() { ",\n" } else { "\n" });
+                }
+
+                pad(out, indent);
+                out.push(']');
+            },
+            Value::Object(fields) if !fields.is_empty() => {
+                out.push_str("{\n");
+
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    pad(out, indent + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write_pretty(out, indent + 1);
+                    out.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+
+                pad(out, indent);
+                out.push('}');
+            },
+            _ => {
+                let _ = write!(out, "{}", self);
+            },
+        }
+    }
+}
+
+fn write_string(out: &mut impl Write, s: &str) {
+    let _ = out.write_char('"');
+
+    for c in s.chars() {
+        let _ = match c {
+            '"' => out.write_str("\\\""),
+            '\\' => out.write_str("\\\\"),
+            '\n' => out.write_str("\\n"),
+            '\r' => out.write_str("\\r"),
+            '\t' => out.write_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32),
+            c => out.write_char(c),
+        };
+    }
+
+    let _ = out.write_char('"');
+}
+
+// Compact form, on a single line
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Value::Number(_) => write!(f, "null"),
+            Value::Str(s) => {
+                write_string(f, s);
+                Ok(())
+            },
+            Value::Array(values) => {
+                write!(f, "[")?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", value)?;
+                }
+
+                write!(f, "]")
+            },
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write_string(f, key);
+                    write!(f, ": {}", value)?;
+                }
+
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+macro_rules! value_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    Value::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+value_from_number!(i32, i64, u32, u64, usize, f64);
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: &str) -> ParseError {
+        let before = &self.text[..self.pos];
+
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+
+        let text: String = self.text[self.pos..]
+            .chars()
+            .take_while(|c| !c.is_whitespace())
+            .take(16)
+            .collect();
+
+        ParseError::new(line, column, &text, reason)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        self.skip_whitespace();
+
+        if self.bytes.get(self.pos) != Some(&c) {
+            return Err(self.error(&format!("expecting '{}'", c as char)));
+        }
+
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn keyword(&mut self, word: &str, value: Value) -> Result<Value, ParseError> {
+        if !self.text[self.pos..].starts_with(word) {
+            return Err(self.error("expecting a value"));
+        }
+
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Value::Str(self.string()?)),
+            Some(b't') => self.keyword("true", Value::Bool(true)),
+            Some(b'f') => self.keyword("false", Value::Bool(false)),
+            Some(b'n') => self.keyword("null", Value::Null),
+            Some(c) if *c == b'-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("expecting a value")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+
+        while self.pos < self.bytes.len() && b"+-.eE0123456789".contains(&self.bytes[self.pos]) {
+            self.pos += 1;
+        }
+
+        match self.text[start..self.pos].parse::<f64>() {
+            Ok(n) => Ok(Value::Number(n)),
+            Err(_) => {
+                self.pos = start;
+                Err(self.error("not a number"))
+            },
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect(b'"')?;
+
+        let mut s = String::new();
+
+        loop {
+            let c = match self.text[self.pos..].chars().next() {
+                Some(c) => c,
+                None => return Err(self.error("unterminated string")),
+            };
+
+            self.pos += c.len_utf8();
+
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escaped = match self.bytes.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'u') => {
+                            let code = self.text.get(self.pos + 1..self.pos + 5)
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+
+                            self.pos += 4;
+                            code
+                        },
+                        _ => return Err(self.error("invalid escape")),
+                    };
+
+                    self.pos += 1;
+                    s.push(escaped);
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        self.expect(b'[')?;
+        self.skip_whitespace();
+
+        let mut values = vec![];
+
+        if self.bytes.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(values));
+                },
+                _ => return Err(self.error("expecting ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.expect(b'{')?;
+        self.skip_whitespace();
+
+        let mut fields = vec![];
+
+        if self.bytes.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                },
+                _ => return Err(self.error("expecting ',' or '}'")),
+            }
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser {
+        text,
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+
+    let value = parser.value()?;
+    parser.skip_whitespace();
+
+    if parser.pos != text.len() {
+        return Err(parser.error("unexpected text after the value"));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = Value::Object(vec![
+            ("name".to_string(), Value::from("day\"15\"\n")),
+            ("runs".to_string(), Value::Array(vec![Value::from(1), Value::from(2.5)])),
+            ("results".to_string(), Value::Array(vec![
+                Value::Object(vec![("ok".to_string(), Value::from(true))]),
+                Value::Null,
+            ])),
+            ("empty".to_string(), Value::Object(vec![])),
+        ]);
+
+        assert_eq!(parse(&value.pretty()), Ok(value.clone()));
+        assert_eq!(parse(&value.to_string()), Ok(value));
+    }
+
+    #[test]
+    fn lookups() {
+        let value = parse(r#"{"a": [1, "é"], "b": -2e3}"#).unwrap();
+
+        assert_eq!(value.get("b").and_then(Value::as_f64), Some(-2000.0));
+        assert_eq!(value.get("a").and_then(Value::as_array).unwrap()[1].as_str(), Some("é"));
+        assert_eq!(value.get("c"), None);
+    }
+
+    #[test]
+    fn errors_have_a_position() {
+        let err = parse("{\n  \"a\": 1,\n  \"b\" 2\n}").unwrap_err();
+
+        assert_eq!((err.line, err.column, err.reason.as_str()), (3, 7, "expecting ':'"));
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("\"abc").is_err());
+        assert!(parse("1 2").is_err());
+    }
+}
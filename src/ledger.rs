@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::json::Value;
+use crate::solution::Answer;
+
+// A known-correct answer. Inputs are told apart by their file name, e.g.
+// "day12.txt" or "day12-example2.txt", and the answers are kept as displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    // Holds the answer of the ledger
+    Mismatch(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "match"),
+            Verdict::Mismatch(expected) if expected.contains('\n') => write!(f, "mismatch, expected:\n{}", expected),
+            Verdict::Mismatch(expected) => write!(f, "mismatch, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger {
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Ledger::default()
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.input == input)
+            .map(|e| e.answer.as_str())
+    }
+
+    pub fn check(&self, day: u32, part: u32, input: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, input) {
+            Some(known) if known == answer.to_string() => Verdict::Match,
+            Some(known) => Verdict::Mismatch(known.to_string()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    // Adds the answer, replacing the one known for the same input and part
+    pub fn record(&mut self, day: u32, part: u32, input: &str, answer: &Answer) {
+        let answer = answer.to_string();
+
+        match self.entries.iter_mut().find(|e| e.day == day && e.part == part && e.input == input) {
+            Some(entry) => entry.answer = answer,
+            None => {
+                self.entries.push(Entry { day, part, input: input.to_string(), answer });
+
+                // Kept sorted so the file changes little between runs
+                self.entries.sort_by(|a, b| (a.day, &a.input, a.part).cmp(&(b.day, &b.input, b.part)));
+            },
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let answers = self.entries
+            .iter()
+            .map(|e| {
+                Value::Object(vec![
+                    ("day".to_string(), Value::from(e.day)),
+                    ("part".to_string(), Value::from(e.part)),
+                    ("input".to_string(), Value::from(e.input.as_str())),
+                    ("answer".to_string(), Value::from(e.answer.as_str())),
+                ])
+            })
+            .collect();
+
+        Value::Object(vec![("answers".to_string(), Value::Array(answers))])
+    }
+
+    // None when the value isn't a file written by `to_json`
+    pub fn from_json(value: &Value) -> Option<Ledger> {
+        let entries = value
+            .get("answers")?
+            .as_array()?
+            .iter()
+            .map(|e| {
+                Some(Entry {
+                    day: e.get("day")?.as_f64()? as u32,
+                    part: e.get("part")?.as_f64()? as u32,
+                    input: e.get("input")?.as_str()?.to_string(),
+                    answer: e.get("answer")?.as_str()?.to_string(),
+                })
+            })
+            .collect::<Option<Vec<Entry>>>()?;
+
+        Some(Ledger { entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    #[test]
+    fn verdicts() {
+        let mut ledger = Ledger::new();
+        ledger.record(1, 1, "day01.txt", &Answer::from(1466));
+
+        assert_eq!(ledger.check(1, 1, "day01.txt", &Answer::from(1466)), Verdict::Match);
+        assert_eq!(ledger.check(1, 1, "day01.txt", &Answer::from(7)), Verdict::Mismatch("1466".to_string()));
+        assert_eq!(ledger.check(1, 1, "day01-example.txt", &Answer::from(7)), Verdict::Unknown);
+        assert_eq!(ledger.check(1, 2, "day01.txt", &Answer::from(1466)), Verdict::Unknown);
+    }
+
+    #[test]
+    fn record_replaces_and_sorts() {
+        let mut ledger = Ledger::new();
+
+        ledger.record(2, 1, "day02.txt", &Answer::from(150));
+        ledger.record(1, 2, "day01.txt", &Answer::from(5));
+        ledger.record(1, 1, "day01.txt", &Answer::from(6));
+        ledger.record(1, 1, "day01.txt", &Answer::from(7));
+
+        let keys: Vec<(u32, u32, &str)> = ledger.entries()
+            .iter()
+            .map(|e| (e.day, e.part, e.answer.as_str()))
+            .collect();
+
+        assert_eq!(keys, [(1, 1, "7"), (1, 2, "5"), (2, 1, "150")]);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut ledger = Ledger::new();
+
+        ledger.record(13, 2, "day13-example.txt", &Answer::Grid(vec!["##".to_string(), "#.".to_string()]));
+        ledger.record(6, 2, "day06.txt", &Answer::from(1613415325809u64));
+
+        let text = ledger.to_json().pretty();
+
+        assert_eq!(Ledger::from_json(&json::parse(&text).unwrap()), Some(ledger));
+        assert_eq!(Ledger::from_json(&json::parse(r#"{"answers": [{"day": 1}]}"#).unwrap()), None);
+    }
+}
@@ -0,0 +1,428 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+
+use aoc2021::bench::{self, Measure};
+use aoc2021::inputs::InputManager;
+use aoc2021::json;
+use aoc2021::ledger::{Ledger, Verdict};
+use aoc2021::registry::{self, Day, DAYS};
+use aoc2021::solution::{Answer, Logger};
+use aoc2021::submit::{Outcome, Submitter};
+
+const USAGE: &str = "\
+usage: aoc2021 [--list] [--day N] [--part 1|2] [--input PATH] [--example] [--variant NAME] [--verbose]
+               [--ledger PATH] [--record] [--submit] [--export DIR] [--repl]
+       aoc2021 --bench N [--day N] [--variant NAME] [--example] [--bench-file PATH] [--threshold PCT]
+
+  --list          list all registered days
+  --day N         day to run (1..=25)
+  --part P        part to run, both parts when omitted
+  --input PATH    input file, defaults to inputs/dayNN.txt (downloaded when
+                  missing, with the session cookie in AOC_SESSION)
+  --example       use inputs/dayNN-example.txt as the default input
+  --variant NAME  alternative implementation of the day (e.g. day15)
+  --verbose       print the solutions diagnostics to stderr
+  --export DIR    write the files showing how the parts were solved, for the
+                  days that have any (e.g. day 15 heatmaps)
+  --repl          interactive mode of the day instead of the parts, for the
+                  days that have one (e.g. day 16 packets)
+
+  --ledger PATH   known-correct answers the results are checked against
+                  (defaults to answers.json)
+  --record        store the answers of the run in the ledger as correct
+  --submit        post the answer of --part for the puzzle input to the site,
+                  a correct one is stored in the ledger
+
+  --bench N          time the parsing and each part over N iterations, every
+                     day (and variant) when --day is omitted
+  --bench-file PATH  results of the previous run, replaced by the new ones
+                     (defaults to bench.json)
+  --threshold PCT    median slowdown flagged as a regression (defaults to 10)";
+
+struct Args {
+    list: bool,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    example: bool,
+    variant: Option<String>,
+    verbose: bool,
+    export: Option<String>,
+    repl: bool,
+    ledger: String,
+    record: bool,
+    submit: bool,
+    bench: Option<u32>,
+    bench_file: String,
+    threshold: u32,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    process::exit(1);
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> u32 {
+    let value = value.unwrap_or_else(|| fail(&format!("missing value for {}", flag)));
+
+    u32::from_str_radix(value.as_str(), 10)
+        .unwrap_or_else(|_| fail(&format!("{} is not a valid value for {}", value, flag)))
+}
+
+fn parse_args() -> Args {
+    let mut args = Args {
+        list: false,
+        day: None,
+        part: None,
+        input: None,
+        example: false,
+        variant: None,
+        verbose: false,
+        export: None,
+        repl: false,
+        ledger: String::from("answers.json"),
+        record: false,
+        submit: false,
+        bench: None,
+        bench_file: String::from("bench.json"),
+        threshold: 10,
+    };
+
+    let mut it = env::args().skip(1);
+
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--list" => args.list = true,
+            "--day" => args.day = Some(parse_number("--day", it.next())),
+            "--part" => args.part = Some(parse_number("--part", it.next())),
+            "--input" => {
+                let path = it.next().unwrap_or_else(|| fail("missing value for --input"));
+                args.input = Some(path);
+            },
+            "--example" => args.example = true,
+            "--variant" => {
+                let name = it.next().unwrap_or_else(|| fail("missing value for --variant"));
+                args.variant = Some(name);
+            },
+            "-v" | "--verbose" => args.verbose = true,
+            "--ledger" => {
+                let path = it.next().unwrap_or_else(|| fail("missing value for --ledger"));
+                args.ledger = path;
+            },
+            "--export" => {
+                let dir = it.next().unwrap_or_else(|| fail("missing value for --export"));
+                args.export = Some(dir);
+            },
+            "--repl" => args.repl = true,
+            "--record" => args.record = true,
+            "--submit" => args.submit = true,
+            "--bench" => args.bench = Some(parse_number("--bench", it.next())),
+            "--bench-file" => {
+                let path = it.next().unwrap_or_else(|| fail("missing value for --bench-file"));
+                args.bench_file = path;
+            },
+            "--threshold" => args.threshold = parse_number("--threshold", it.next()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            _ => fail(&format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    args
+}
+
+fn print_days() {
+    for day in DAYS {
+        println!("{:>2}  {}", day.number, day.name);
+    }
+}
+
+// Ledger key of the input, its file name
+fn input_name(filename: &str) -> String {
+    Path::new(filename)
+        .file_name()
+        .map_or(filename.to_string(), |name| name.to_string_lossy().into_owned())
+}
+
+fn run_part(day: &Day, part: u32, filename: &str, content: &str, logger: &Logger, ledger: &Ledger) -> Option<Answer> {
+    match day.solve(part, content, logger) {
+        Some(Ok(answer)) => {
+            let verdict = ledger.check(day.number, part, &input_name(filename), &answer);
+
+            match (&answer, &verdict) {
+                // Grids start on their own line so the rows stay aligned
+                (Answer::Grid(_), Verdict::Mismatch(_)) => println!("{} part {}:\n{}\n[{}]", day.name, part, answer, verdict),
+                (Answer::Grid(_), _) => println!("{} part {}: [{}]\n{}", day.name, part, verdict, answer),
+                _ => println!("{} part {}: {} [{}]", day.name, part, answer, verdict),
+            }
+
+            Some(answer)
+        },
+        Some(Err(e)) => {
+            eprintln!("error: {}", e.in_file(filename));
+            process::exit(1);
+        },
+        None => {
+            println!("{} has no part {}", day.name, part);
+            None
+        },
+    }
+}
+
+// A missing ledger is an empty one, a broken one stops the run rather than
+// being overwritten
+fn read_ledger(path: &str) -> Ledger {
+    if !Path::new(path).exists() {
+        return Ledger::new();
+    }
+
+    fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path, e))
+        .and_then(|text| json::parse(&text).map_err(|e| e.in_file(path).to_string()))
+        .and_then(|value| Ledger::from_json(&value).ok_or(format!("{} is not an answers ledger", path)))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        })
+}
+
+fn write_ledger(path: &str, ledger: &Ledger) {
+    fs::write(path, ledger.to_json().pretty())
+        .unwrap_or_else(|e| fail(&format!("could not write {}: {}", path, e)));
+}
+
+fn export_part(day: &Day, part: u32, filename: &str, content: &str, dir: &str) {
+    let files = match day.solution.export(content, part) {
+        Some(Ok(files)) => files,
+        Some(Err(e)) => {
+            eprintln!("error: {}", e.in_file(filename));
+            process::exit(1);
+        },
+        None => {
+            eprintln!("{} has nothing to export", day.name);
+            return;
+        },
+    };
+
+    fs::create_dir_all(dir).unwrap_or_else(|e| fail(&format!("could not create {}: {}", dir, e)));
+
+    for file in files {
+        let path = Path::new(dir).join(format!("{}-part{}-{}", day.name, part, file.name));
+
+        fs::write(&path, &file.data)
+            .unwrap_or_else(|e| fail(&format!("could not write {}: {}", path.display(), e)));
+
+        println!("wrote {}", path.display());
+    }
+}
+
+fn submit_answer(args: &Args, day: &Day, part: u32, filename: &str, answer: &Answer, ledger: &mut Ledger) {
+    let input = input_name(filename);
+
+    // The ledger already knows, the site would only refuse it or count a wrong answer
+    match ledger.check(day.number, part, &input, answer) {
+        Verdict::Match => {
+            println!("not submitting, the answer is already known to be correct");
+            return;
+        },
+        Verdict::Mismatch(_) => {
+            println!("not submitting, the ledger has a different correct answer");
+            return;
+        },
+        Verdict::Unknown => {},
+    }
+
+    let reply = Submitter::from_env()
+        .submit(day.number, part, answer)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+
+    println!("{}", reply.message);
+
+    if reply.outcome == Outcome::Correct {
+        ledger.record(day.number, part, &input, answer);
+        write_ledger(&args.ledger, ledger);
+    }
+}
+
+// Name of the input, for the diagnostics, and its content
+fn read_input(args: &Args, inputs: &InputManager, day: &Day) -> Result<(String, String), String> {
+    if let Some(path) = &args.input {
+        return fs::read_to_string(path)
+            .map(|content| (path.clone(), content))
+            .map_err(|e| format!("could not read {}: {}", path, e));
+    }
+
+    let filename = inputs.path(day.number, args.example).display().to_string();
+
+    inputs.read(day.number, args.example)
+        .map(|content| (filename, content))
+        .map_err(|e| e.to_string())
+}
+
+fn read_previous_bench(path: &str) -> Vec<Measure> {
+    if !Path::new(path).exists() {
+        return vec![];
+    }
+
+    let previous = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| json::parse(&text).map_err(|e| e.in_file(path).to_string()))
+        .and_then(|value| bench::from_json(&value).ok_or(format!("{} is not a benchmark file", path)));
+
+    previous.unwrap_or_else(|e| {
+        eprintln!("warning: ignoring the previous results, {}", e);
+        vec![]
+    })
+}
+
+fn run_bench(args: &Args, iterations: u32) {
+    let days: Vec<&Day> = match (args.day, &args.variant) {
+        (Some(n), Some(name)) => {
+            let day = registry::find(n, Some(name.as_str()))
+                .unwrap_or_else(|| fail(&format!("day {} has no variant {}", n, name)));
+
+            vec![day]
+        },
+        (Some(n), None) => DAYS.iter().filter(|d| d.number == n).collect(),
+        (None, _) => DAYS.iter().collect(),
+    };
+
+    if days.is_empty() {
+        fail("no days to benchmark");
+    }
+
+    if args.input.is_some() && days.len() > 1 {
+        fail("--input needs a single day, use --variant to pick one");
+    }
+
+    let inputs = InputManager::from_env();
+    let mut measures = vec![];
+
+    for day in days {
+        let (filename, content) = match read_input(args, &inputs, day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping {}: {}", day.name, e);
+                continue;
+            },
+        };
+
+        eprintln!("benchmarking {} ({} iterations)", day.name, iterations);
+
+        match bench::bench_day(day, &content, iterations as usize) {
+            Ok(m) => measures.extend(m),
+            Err(e) => {
+                eprintln!("error: {}", e.in_file(&filename));
+                process::exit(1);
+            },
+        }
+    }
+
+    let previous = read_previous_bench(&args.bench_file);
+    let threshold = args.threshold as f64 / 100.0;
+
+    let (table, regressions) = bench::report(&measures, &previous, threshold);
+
+    print!("{}", table);
+
+    if regressions > 0 {
+        println!("{} regression(s) above {}% against {}", regressions, args.threshold, args.bench_file);
+    }
+
+    let merged = bench::merge(&previous, &measures);
+
+    fs::write(&args.bench_file, bench::to_json(&merged).pretty())
+        .unwrap_or_else(|e| fail(&format!("could not write {}: {}", args.bench_file, e)));
+}
+
+fn main() {
+    let args = parse_args();
+
+    if let Some(iterations) = args.bench {
+        if iterations == 0 {
+            fail("--bench needs at least one iteration");
+        }
+
+        run_bench(&args, iterations);
+        return;
+    }
+
+    let number = match args.day {
+        Some(n) if !args.list => n,
+        _ => {
+            print_days();
+            return;
+        },
+    };
+
+    let day = registry::find(number, args.variant.as_deref())
+        .unwrap_or_else(|| fail(&format!("day {} is not registered", number)));
+
+    if args.repl {
+        let stdin = io::stdin();
+
+        match day.solution.repl(&mut stdin.lock(), &mut io::stdout()) {
+            Some(Ok(())) => {},
+            Some(Err(e)) => fail(&format!("{}: {}", day.name, e)),
+            None => fail(&format!("{} has no interactive mode", day.name)),
+        }
+
+        return;
+    }
+
+    let inputs = InputManager::from_env();
+
+    let (filename, content) = read_input(&args, &inputs, day)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+
+    let logger =
+        if args.verbose {
+            Logger::stderr()
+        } else {
+            Logger::silent()
+        };
+
+    let parts = match args.part {
+        Some(p @ (1 | 2)) => vec![p],
+        Some(p) => fail(&format!("{} is not a valid part", p)),
+        None => vec![1, 2],
+    };
+
+    if args.submit && (args.part.is_none() || args.example || args.input.is_some()) {
+        fail("--submit needs a --part and the puzzle input");
+    }
+
+    let mut ledger = read_ledger(&args.ledger);
+
+    for part in parts {
+        let answer = match run_part(day, part, &filename, &content, &logger, &ledger) {
+            Some(answer) => answer,
+            None => continue,
+        };
+
+        if let Some(dir) = &args.export {
+            export_part(day, part, &filename, &content, dir);
+        }
+
+        if args.submit {
+            submit_answer(&args, day, part, &filename, &answer, &mut ledger);
+        } else if args.record {
+            ledger.record(day.number, part, &input_name(&filename), &answer);
+        }
+    }
+
+    if args.record && !args.submit {
+        write_ledger(&args.ledger, &ledger);
+    }
+}
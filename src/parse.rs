@@ -0,0 +1,181 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+// Position and text of the first malformed piece of an input. Lines and
+// columns start at 1, the file is only known by whoever read the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, reason: &str) -> Self {
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    // Error for input that stops before something required shows up
+    pub fn end_of_input(content: &str, reason: &str) -> Self {
+        ParseError::new(content.lines().count() + 1, 1, "", reason)
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+
+        write!(f, "{}:{}:{}: {}", file, self.line, self.column, self.reason)?;
+
+        if !self.text.is_empty() {
+            write!(f, " '{}'", self.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    // Column of `part`, which is expected to be a slice of this line. Anything
+    // else is reported at the start of the line.
+    pub fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = part.as_ptr() as usize;
+
+        if pos >= start && pos <= start + self.text.len() {
+            pos - start + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn error(&self, part: &str, reason: &str) -> ParseError {
+        ParseError::new(self.number, self.column_of(part), part, reason)
+    }
+
+    // Error at a byte offset of the line, for parsers walking over bytes
+    pub fn error_at(&self, offset: usize, len: usize, reason: &str) -> ParseError {
+        let end = (offset + len).min(self.text.len());
+        let text = self.text.get(offset..end).unwrap_or("");
+
+        ParseError::new(self.number, offset + 1, text, reason)
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &str) -> Result<T, ParseError> {
+        part.parse::<T>()
+            .map_err(|_| self.error(part, "not a number"))
+    }
+
+    // Splits the line in exactly `count` fields
+    pub fn fields(&self, separator: &str, count: usize) -> Result<Vec<&'a str>, ParseError> {
+        let fields: Vec<&'a str> = self.text.split(separator).collect();
+
+        if fields.len() != count {
+            let reason = format!("expecting {} fields separated by '{}'", count, separator);
+            return Err(self.error(self.text, &reason));
+        }
+
+        Ok(fields)
+    }
+
+    pub fn digits(&self) -> Result<Vec<u32>, ParseError> {
+        self.text
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .ok_or_else(|| self.error_at(i, c.len_utf8(), "not a digit"))
+            })
+            .collect()
+    }
+}
+
+pub fn lines(content: &str) -> impl Iterator<Item = Line<'_>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line { number: i + 1, text })
+}
+
+// Single line inputs, like comma separated lists
+pub fn first_line(content: &str) -> Result<Line<'_>, ParseError> {
+    lines(content)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(content, "empty input"))
+}
+
+// Rectangular grid of single digits (days 9, 11 and 15)
+pub fn digit_grid(content: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(content, |c| c.to_digit(10), "not a digit")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_errors_point_at_the_field() {
+        let line = lines("1,2\n3,x4").nth(1).unwrap();
+        let fields = line.fields(",", 2).unwrap();
+
+        let err = line.parse::<i32>(fields[1]).unwrap_err();
+
+        assert_eq!(err, ParseError::new(2, 3, "x4", "not a number"));
+        assert_eq!(err.in_file("a.txt").to_string(), "a.txt:2:3: not a number 'x4'");
+    }
+
+    #[test]
+    fn wrong_field_count() {
+        let line = first_line("1,2,3").unwrap();
+        let err = line.fields(",", 2).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, "1,2,3"));
+    }
+
+    #[test]
+    fn digits() {
+        let line = first_line("12a4").unwrap();
+
+        assert_eq!(line.digits().unwrap_err(), ParseError::new(1, 3, "a", "not a digit"));
+        assert_eq!(first_line("908").unwrap().digits().unwrap(), vec![9, 0, 8]);
+    }
+
+    #[test]
+    fn ragged_grid() {
+        let err = digit_grid("123\n45\n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(digit_grid("12\n34").unwrap().to_string(), "12\n34");
+    }
+
+    #[test]
+    fn end_of_input() {
+        let err = ParseError::end_of_input("a\nb\n", "missing section");
+
+        assert_eq!(err.to_string(), "<input>:3:1: missing section");
+        assert!(first_line("").is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::*;
+use crate::parse::ParseError;
+use crate::solution::{Answer, Logger, Solution};
+
+pub struct Day {
+    pub number: u32,
+    pub name: &'static str,
+
+    pub solution: &'static dyn Solution,
+}
+
+impl Day {
+    pub fn solve(&self, part: u32, content: &str, logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        match part {
+            1 => Some(self.solution.part1(content, logger)),
+            2 => self.solution.part2(content, logger),
+            _ => None,
+        }
+    }
+}
+
+const fn day(number: u32, name: &'static str, solution: &'static dyn Solution) -> Day {
+    Day {
+        number,
+        name,
+        solution,
+    }
+}
+
+// The first entry of a day is its default solution, the others are alternative
+// implementations that can be picked by name.
+pub static DAYS: &[Day] = &[
+    day(1,  "day01",   &day01::Day01),
+    day(2,  "day02",   &day02::Day02),
+    day(3,  "day03",   &day03::Day03),
+    day(4,  "day04",   &day04::Day04),
+    day(5,  "day05",   &day05::Day05),
+    day(6,  "day06",   &day06::Day06),
+    day(7,  "day07",   &day07::Day07),
+    day(8,  "day08",   &day08::Day08),
+    day(9,  "day09",   &day09::Day09),
+    day(10, "day10",   &day10::Day10),
+    day(11, "day11",   &day11::Day11),
+    day(12, "day12",   &day12::Day12),
+    day(13, "day13",   &day13::Day13),
+    day(14, "day14",   &day14::Day14),
+    day(15, "day15v4", &day15v4::Day15v4),
+    day(15, "day15",   &day15::Day15),
+    day(16, "day16",   &day16::Day16),
+    day(17, "day17",   &day17::Day17),
+    day(18, "day18v2", &day18v2::Day18v2),
+    day(18, "day18",   &day18::Day18),
+    day(19, "day19",   &day19::Day19),
+    day(20, "day20",   &day20::Day20),
+    day(21, "day21",   &day21::Day21),
+    day(22, "day22",   &day22::Day22),
+    day(23, "day23",   &day23::Day23),
+    day(24, "day24v2", &day24v2::Day24v2),
+    day(24, "day24",   &day24::Day24),
+    day(25, "day25",   &day25::Day25),
+];
+
+pub fn find(number: u32, name: Option<&str>) -> Option<&'static Day> {
+    DAYS.iter().find(|d| {
+        d.number == number && name.map_or(true, |n| d.name == n)
+    })
+}
@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+// Weighted directed graph explored from a start node, the nodes are built
+// on demand so the graph can be a whole state space
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    // Nodes reachable in one move, with the cost of the move
+    fn neighbors(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+
+    // Lower bound of the cost from `node` to a goal. A* never goes back to a
+    // node it has closed, so it finds the cheapest path only when the
+    // heuristic is also consistent: it drops by at most the cost of each
+    // edge, h(a) <= cost(a, b) + h(b). 0 is a plain Dijkstra.
+    fn heuristic(&self, _node: &Self::Node) -> u64 {
+        0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    // From the start to the goal, both included
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+// What a search went through to find its path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exploration<N> {
+    pub path: Option<Path<N>>,
+
+    // Nodes whose cheapest cost from the start is known, in the order they
+    // were visited
+    pub visited: Vec<(N, u64)>,
+}
+
+// Cheapest path from `start` to a node accepted by `is_goal`, ignoring the
+// heuristic of the graph
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Option<Path<G::Node>> {
+    search(graph, start, is_goal, |_| 0).path
+}
+
+// Same as `dijkstra`, visiting first the nodes the heuristic finds closer to
+// a goal
+pub fn astar<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Option<Path<G::Node>> {
+    astar_explored(graph, start, is_goal).path
+}
+
+// `astar` keeping the visited nodes
+pub fn astar_explored<G: Graph>(graph: &G, start: G::Node, is_goal: impl Fn(&G::Node) -> bool) -> Exploration<G::Node> {
+    search(graph, start, is_goal, |node| graph.heuristic(node))
+}
+
+fn search<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+) -> Exploration<G::Node> {
+    // Nodes are numbered as they are found, the heap only holds numbers
+    let mut nodes: Vec<G::Node> = vec![start.clone()];
+    let mut index: HashMap<G::Node, usize> = HashMap::from([(start.clone(), 0)]);
+
+    let mut costs: Vec<u64> = vec![0];
+    let mut prev: Vec<Option<usize>> = vec![None];
+    let mut done: Vec<bool> = vec![false];
+    let mut order: Vec<usize> = vec![];
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((heuristic(&start), 0u64, 0usize)));
+
+    while let Some(Reverse((_, cost, current))) = heap.pop() {
+        // Stale entry, the node was reached again more cheaply
+        if done[current] || cost > costs[current] {
+            continue;
+        }
+
+        done[current] = true;
+        order.push(current);
+
+        if is_goal(&nodes[current]) {
+            let mut path = vec![];
+            let mut node = Some(current);
+
+            while let Some(i) = node {
+                path.push(nodes[i].clone());
+                node = prev[i];
+            }
+
+            path.reverse();
+
+            return Exploration {
+                path: Some(Path { nodes: path, cost }),
+                visited: visited(&nodes, &costs, &order),
+            };
+        }
+
+        for (next, step) in graph.neighbors(&nodes[current]) {
+            let next_cost = cost + step;
+
+            let i = match index.get(&next) {
+                Some(&i) => i,
+                None => {
+                    let i = nodes.len();
+
+                    index.insert(next.clone(), i);
+                    nodes.push(next);
+                    costs.push(u64::MAX);
+                    prev.push(None);
+                    done.push(false);
+
+                    i
+                },
+            };
+
+            if !done[i] && next_cost < costs[i] {
+                costs[i] = next_cost;
+                prev[i] = Some(current);
+
+                heap.push(Reverse((next_cost + heuristic(&nodes[i]), next_cost, i)));
+            }
+        }
+    }
+
+    Exploration {
+        path: None,
+        visited: visited(&nodes, &costs, &order),
+    }
+}
+
+fn visited<N: Clone>(nodes: &[N], costs: &[u64], order: &[usize]) -> Vec<(N, u64)> {
+    order.iter().map(|&i| (nodes[i].clone(), costs[i])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Edges of a small road map, the direct road from a to d is the most
+    // expensive way there
+    struct Roads;
+
+    impl Graph for Roads {
+        type Node = char;
+
+        fn neighbors(&self, node: &char) -> Vec<(char, u64)> {
+            match node {
+                'a' => vec![('b', 1), ('c', 4), ('d', 10)],
+                'b' => vec![('c', 2), ('e', 7)],
+                'c' => vec![('d', 3)],
+                'd' => vec![('e', 1)],
+                _ => vec![],
+            }
+        }
+    }
+
+    // Points of a line, moving by one costs 1
+    struct Line;
+
+    impl Graph for Line {
+        type Node = i32;
+
+        fn neighbors(&self, node: &i32) -> Vec<(i32, u64)> {
+            vec![(node - 1, 1), (node + 1, 1)]
+        }
+
+        fn heuristic(&self, node: &i32) -> u64 {
+            (10 - node).unsigned_abs() as u64
+        }
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let path = dijkstra(&Roads, 'a', |&n| n == 'd').unwrap();
+
+        assert_eq!(path, Path { nodes: vec!['a', 'b', 'c', 'd'], cost: 6 });
+        assert_eq!(dijkstra(&Roads, 'a', |&n| n == 'e').unwrap().cost, 7);
+        assert_eq!(dijkstra(&Roads, 'a', |&n| n == 'a').unwrap().nodes, ['a']);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(dijkstra(&Roads, 'c', |&n| n == 'a'), None);
+        assert_eq!(astar(&Roads, 'e', |&n| n == 'a'), None);
+    }
+
+    #[test]
+    fn heuristic_keeps_the_cost() {
+        let path = astar(&Line, 0, |&n| n == 10).unwrap();
+
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.nodes, (0..=10).collect::<Vec<i32>>());
+        assert_eq!(astar(&Roads, 'a', |&n| n == 'e'), dijkstra(&Roads, 'a', |&n| n == 'e'));
+    }
+
+    #[test]
+    fn visited_nodes() {
+        let exploration = astar_explored(&Roads, 'a', |&n| n == 'd');
+
+        assert_eq!(exploration.visited, [('a', 0), ('b', 1), ('c', 3), ('d', 6)]);
+
+        // Only one of the directions is explored toward the goal
+        let exploration = astar_explored(&Line, 0, |&n| n == 3);
+        assert!(exploration.visited.iter().all(|&(n, _)| n >= 0));
+    }
+}
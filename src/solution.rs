@@ -0,0 +1,133 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::Mutex;
+
+use crate::parse::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+
+    // Rows of a rendered image, e.g. the letters of day13's folded paper
+    Grid(Vec<String>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Str(value.to_string())
+    }
+}
+
+// Destination for the diagnostic output of the solutions. A silent logger
+// discards everything without formatting it.
+pub struct Logger {
+    sink: Option<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl Logger {
+    pub fn silent() -> Self {
+        Logger { sink: None }
+    }
+
+    pub fn stderr() -> Self {
+        Logger::new(io::stderr())
+    }
+
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Logger {
+            sink: Some(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    pub fn log(&self, args: fmt::Arguments) {
+        if let Some(sink) = &self.sink {
+            let mut writer = sink.lock().unwrap();
+
+            // Diagnostics are best effort, a broken sink must not stop a solution
+            let _ = writeln!(writer, "{}", args);
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! log {
+    ($logger:expr, $($arg:tt)*) => {
+        $logger.log(format_args!($($arg)*))
+    };
+}
+
+// File produced by `Solution::export`, e.g. a picture of how a part was solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    // Without directory, the runner adds the day and part in front
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl Export {
+    pub fn new(name: &str, data: impl Into<Vec<u8>>) -> Self {
+        Export {
+            name: name.to_string(),
+            data: data.into(),
+        }
+    }
+}
+
+pub trait Solution: Sync {
+    // Reads the input without solving anything, so the parsing can be timed
+    // apart from the parts
+    fn parse(&self, content: &str) -> Result<(), ParseError>;
+
+    fn part1(&self, content: &str, logger: &Logger) -> Result<Answer, ParseError>;
+
+    // Not every day has a second part (day25, the first day15 attempt)
+    fn part2(&self, _content: &str, _logger: &Logger) -> Option<Result<Answer, ParseError>> {
+        None
+    }
+
+    // Files showing the work behind a part, for the days that have any
+    fn export(&self, _content: &str, _part: u32) -> Option<Result<Vec<Export>, ParseError>> {
+        None
+    }
+
+    // Interactive mode reading commands from `input`, for the days that have
+    // one
+    fn repl(&self, _input: &mut dyn BufRead, _output: &mut dyn Write) -> Option<io::Result<()>> {
+        None
+    }
+}
@@ -0,0 +1,201 @@
+use std::env;
+use std::fmt;
+
+use crate::http::{self, HttpClient, HttpError};
+use crate::inputs::{DEFAULT_BASE_URL, YEAR};
+use crate::solution::Answer;
+
+#[derive(Debug)]
+pub enum SubmitError {
+    NoSession,
+
+    // Grids are letters drawn with '#', they have to be read and sent by hand
+    GridAnswer,
+
+    Http(String, HttpError),
+    Status(String, u16),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::NoSession => write!(f, "set AOC_SESSION to submit answers"),
+            SubmitError::GridAnswer => write!(f, "grid answers must be read and submitted by hand"),
+            SubmitError::Http(url, e) => write!(f, "could not post to {}: {}", url, e),
+            SubmitError::Status(url, status) => write!(f, "could not post to {}: status {}", url, status),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+
+    // Answers are rate limited, the message says how long to wait
+    TooSoon,
+
+    // The part was already solved, or the first one isn't yet
+    WrongLevel,
+
+    // A page the runner doesn't know about, the message tells what happened
+    Unrecognized,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+
+    // Text of the page, e.g. "That's not the right answer; your answer is too high..."
+    pub message: String,
+}
+
+// Text of the <article> of the answer page, without the markup
+pub fn page_message(body: &str) -> String {
+    let article = match (body.find("<article"), body.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &body[start..end],
+        _ => body,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+pub fn read_reply(body: &str) -> Reply {
+    let message = page_message(body);
+
+    let outcome =
+        if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("That's not the right answer") {
+            Outcome::Incorrect
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::TooSoon
+        } else if message.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unrecognized
+        };
+
+    Reply { outcome, message }
+}
+
+// Posts answers to the site, like `InputManager` downloads the inputs
+pub struct Submitter {
+    base_url: String,
+    session: Option<String>,
+    client: Box<dyn HttpClient>,
+}
+
+impl Submitter {
+    pub fn new() -> Self {
+        Submitter {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            client: http::client_for(DEFAULT_BASE_URL),
+        }
+    }
+
+    // Same variables as `InputManager::from_env`
+    pub fn from_env() -> Self {
+        let mut submitter = Submitter::new();
+
+        if let Ok(url) = env::var("AOC_BASE_URL") {
+            submitter = submitter.with_base_url(&url);
+        }
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            submitter = submitter.with_session(&session);
+        }
+
+        submitter
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        let session = session.trim();
+
+        self.session = if session.is_empty() { None } else { Some(session.to_string()) };
+        self
+    }
+
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self.client = http::client_for(&self.base_url);
+        self
+    }
+
+    pub fn with_client(mut self, client: Box<dyn HttpClient>) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn url(&self, day: u32) -> String {
+        format!("{}/{}/day/{}/answer", self.base_url, YEAR, day)
+    }
+
+    pub fn submit(&self, day: u32, part: u32, answer: &Answer) -> Result<Reply, SubmitError> {
+        if let Answer::Grid(_) = answer {
+            return Err(SubmitError::GridAnswer);
+        }
+
+        let session = self.session.as_ref().ok_or(SubmitError::NoSession)?;
+
+        let url = self.url(day);
+        let level = part.to_string();
+        let answer = answer.to_string();
+
+        let form = http::form_encode(&[("level", &level), ("answer", &answer)]);
+
+        let response = self.client
+            .post_form(&url, &format!("session={}", session), &form)
+            .map_err(|e| SubmitError::Http(url.clone(), e))?;
+
+        if response.status != 200 {
+            return Err(SubmitError::Status(url, response.status));
+        }
+
+        Ok(read_reply(&response.body))
+    }
+}
+
+impl Default for Submitter {
+    fn default() -> Self {
+        Submitter::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies() {
+        let page = "<html><main>\n<article><p>That's not the right answer; your answer is too high.  \
+                    Please wait one minute. [<a href=\"/2021/day/1\">Return to Day 1</a>]</p></article>\n</main></html>";
+
+        let reply = read_reply(page);
+
+        assert_eq!(reply.outcome, Outcome::Incorrect);
+        assert_eq!(
+            reply.message,
+            "That's not the right answer; your answer is too high. Please wait one minute. [Return to Day 1]"
+        );
+
+        assert_eq!(read_reply("<article><p>That's the right answer!</p></article>").outcome, Outcome::Correct);
+        assert_eq!(read_reply("<article><p>You gave an answer too recently</p></article>").outcome, Outcome::TooSoon);
+        assert_eq!(read_reply("Internal error").outcome, Outcome::Unrecognized);
+    }
+}
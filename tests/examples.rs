@@ -0,0 +1,182 @@
+use std::fs;
+use std::path::Path;
+
+use aoc2021::inputs::InputManager;
+use aoc2021::json;
+use aoc2021::ledger::Ledger;
+use aoc2021::registry;
+use aoc2021::solution::Logger;
+
+struct Example {
+    day: u32,
+    variant: Option<&'static str>,
+    file: &'static str,
+
+    // Expected answers as displayed, grids have their rows joined by '\n'
+    part1: &'static str,
+    part2: Option<&'static str>,
+}
+
+const fn example(day: u32, file: &'static str, part1: &'static str, part2: &'static str) -> Example {
+    Example {
+        day,
+        variant: None,
+        file,
+        part1,
+        part2: Some(part2),
+    }
+}
+
+// day16-example.txt and day18-example.txt hold a single case out of many, they
+// are covered by the unit tests of those days. The day24 examples are not MONAD
+// programs, their answers are the largest and smallest digits they accept.
+const EXAMPLES: &[Example] = &[
+    example(1,  "day01-example.txt",    "7",       "5"),
+    example(2,  "day02-example.txt",    "150",     "900"),
+    example(3,  "day03-example.txt",    "198",     "230"),
+    example(4,  "day04-example.txt",    "4512",    "1924"),
+    example(5,  "day05-example.txt",    "5",       "12"),
+    example(6,  "day06-example.txt",    "5934",    "26984457539"),
+    example(7,  "day07-example.txt",    "37",      "168"),
+    example(8,  "day08-example.txt",    "26",      "61229"),
+    example(9,  "day09-example.txt",    "15",      "1134"),
+    example(10, "day10-example.txt",    "26397",   "288957"),
+    example(11, "day11-example.txt",    "1656",    "195"),
+    example(12, "day12-example.txt",    "10",      "36"),
+    example(12, "day12-example2.txt",   "19",      "103"),
+    example(12, "day12-example3.txt",   "226",     "3509"),
+    example(13, "day13-example.txt",    "17",      "#####\n#...#\n#...#\n#...#\n#####\n.....\n....."),
+    example(14, "day14-example.txt",    "1588",    "2188189693529"),
+    example(15, "day15-example.txt",    "40",      "315"),
+    Example {
+        day: 15,
+        variant: Some("day15"),
+        file: "day15-example.txt",
+        part1: "40",
+        part2: None,
+    },
+    example(17, "day17-example.txt",    "45",      "112"),
+    example(18, "day18-example9.txt",   "4140",    "3993"),
+    Example {
+        day: 18,
+        variant: Some("day18"),
+        file: "day18-example9.txt",
+        part1: "4140",
+        part2: Some("3993"),
+    },
+    example(19, "day19-example.txt",    "79",      "3621"),
+    example(20, "day20-example.txt",    "35",      "3351"),
+    example(21, "day21-example.txt",    "739785",  "444356092776315"),
+    example(22, "day22-example.txt",    "590784",  "39769202357779"),
+    example(22, "day22-example2.txt",   "474140",  "2758514936282235"),
+    example(23, "day23-example.txt",    "12521",   "44169"),
+    example(24, "day24-example.txt",    "9",       "1"),
+    example(24, "day24-example2.txt",   "99",      "11"),
+    example(24, "day24-example3.txt",   "8",       "2"),
+    Example {
+        day: 25,
+        variant: None,
+        file: "day25-example.txt",
+        part1: "58",
+        part2: None,
+    },
+];
+
+fn check(filter: impl Fn(&Example) -> bool) {
+    let logger = Logger::silent();
+    let inputs = InputManager::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+    let mut failures = vec![];
+
+    for ex in EXAMPLES.iter().filter(|ex| filter(ex)) {
+        let day = registry::find(ex.day, ex.variant)
+            .unwrap_or_else(|| panic!("day {} ({:?}) is not registered", ex.day, ex.variant));
+
+        let content = inputs.read_file(ex.file).unwrap_or_else(|e| panic!("{}", e));
+
+        let expected = [Some(ex.part1), ex.part2];
+
+        for (i, expected) in expected.iter().enumerate() {
+            let part = i as u32 + 1;
+            let answer = match day.solve(part, &content, &logger) {
+                Some(Ok(answer)) => Some(answer.to_string()),
+                Some(Err(e)) => Some(format!("error: {}", e.in_file(ex.file))),
+                None => None,
+            };
+
+            if answer.as_deref() != *expected {
+                failures.push(format!(
+                    "{} part {} on {}: expected {:?}, got {:?}",
+                    day.name, part, ex.file, expected, answer
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn every_registered_day_has_an_example_or_a_reason() {
+    for day in registry::DAYS {
+        let covered = EXAMPLES.iter().any(|ex| ex.day == day.number);
+
+        assert!(covered || day.number == 16, "{} has no example", day.name);
+    }
+}
+
+// The answers ledger shipped with the repository knows the examples too
+#[test]
+fn ledger_agrees_with_examples() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.json");
+    let text = fs::read_to_string(&path).unwrap();
+    let ledger = Ledger::from_json(&json::parse(&text).unwrap()).expect("not an answers ledger");
+
+    for ex in EXAMPLES {
+        let expected = [Some(ex.part1), ex.part2];
+
+        for (i, expected) in expected.iter().enumerate() {
+            if let Some(expected) = expected {
+                let part = i as u32 + 1;
+                assert_eq!(ledger.get(ex.day, part, ex.file), Some(*expected), "day {} part {} on {}", ex.day, part, ex.file);
+            }
+        }
+    }
+}
+
+#[test]
+fn days_01_to_08() {
+    check(|ex| ex.day <= 8);
+}
+
+#[test]
+fn days_09_to_15() {
+    check(|ex| (9..=15).contains(&ex.day));
+}
+
+#[test]
+fn days_16_to_20() {
+    check(|ex| (16..=20).contains(&ex.day));
+}
+
+#[test]
+fn days_21_to_25() {
+    check(|ex| ex.day >= 21);
+}
+
+// Inputs too short to hold an answer are parse errors, not panics
+#[test]
+fn short_inputs() {
+    let logger = Logger::silent();
+    let cases = [
+        (1, 1, ""),
+        (1, 2, "199\n200\n208\n"),
+        (3, 2, "10110\n"),
+        (9, 2, "5\n"),
+    ];
+
+    for (number, part, content) in cases {
+        let day = registry::find(number, None).unwrap();
+
+        assert!(matches!(day.solve(part, content, &logger), Some(Err(_))), "day {} part {} on {:?}", number, part, content);
+    }
+}
@@ -0,0 +1,155 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use aoc2021::inputs::{InputError, InputManager};
+
+// Stand-in for the puzzle site, answers the input of day 1 to the "abc"
+// session and keeps the requested paths
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let requests = Arc::new(Mutex::new(vec![]));
+        let log = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut path = String::new();
+                let mut cookie = String::new();
+
+                loop {
+                    let mut line = String::new();
+
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+
+                    if let Some(request) = line.strip_prefix("GET ") {
+                        path = request.split(' ').next().unwrap_or("").to_string();
+                    } else if let Some(value) = line.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_string();
+                    }
+                }
+
+                log.lock().unwrap().push(path.clone());
+
+                let (status, body) = match (path.as_str(), cookie.as_str()) {
+                    (_, c) if c != "session=abc" => ("400 Bad Request", "Puzzle inputs differ by user."),
+                    ("/2021/day/1/input", _) => ("200 OK", "199\n200\n208\n"),
+                    _ => ("404 Not Found", "Not unlocked yet"),
+                };
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body
+                );
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc2021-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn downloads_missing_input_once() {
+    let server = MockServer::start();
+    let dir = temp_dir("download");
+
+    let inputs = InputManager::new(&dir)
+        .with_base_url(&server.url)
+        .with_session("abc\n");
+
+    assert_eq!(inputs.read(1, false).unwrap(), "199\n200\n208\n");
+    assert_eq!(fs::read_to_string(dir.join("day01.txt")).unwrap(), "199\n200\n208\n");
+
+    // Cached from now on
+    assert_eq!(inputs.read(1, false).unwrap(), "199\n200\n208\n");
+    assert_eq!(server.request_count(), 1);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn failed_downloads_leave_nothing_behind() {
+    let server = MockServer::start();
+    let dir = temp_dir("failed");
+
+    let inputs = InputManager::new(&dir).with_base_url(&server.url);
+
+    let wrong_session = inputs.with_session("xyz");
+    match wrong_session.read(1, false) {
+        Err(InputError::Status(_, 400, body)) => assert!(body.contains("differ by user")),
+        other => panic!("expecting a 400, got {:?}", other),
+    }
+
+    let inputs = InputManager::new(&dir).with_base_url(&server.url).with_session("abc");
+    match inputs.read(2, false) {
+        Err(e @ InputError::Status(_, 404, _)) => {
+            assert!(e.to_string().ends_with("/2021/day/2/input: status 404 (Not unlocked yet)"));
+        },
+        other => panic!("expecting a 404, got {:?}", other),
+    }
+
+    assert_eq!(server.request_count(), 2);
+    assert!(!dir.join("day01.txt").exists() && !dir.join("day02.txt").exists());
+}
+
+#[test]
+fn no_request_without_session_or_for_examples() {
+    let server = MockServer::start();
+    let dir = temp_dir("offline");
+
+    let inputs = InputManager::new(&dir).with_base_url(&server.url);
+
+    assert!(matches!(inputs.read(1, false), Err(InputError::NoSession(1, _))));
+
+    let inputs = inputs.with_session("abc");
+    assert!(matches!(inputs.read(1, true), Err(InputError::NoExample(_))));
+
+    assert_eq!(server.request_count(), 0);
+}
+
+#[test]
+fn cached_inputs_need_no_server() {
+    let dir = temp_dir("cached");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("day05.txt"), "0,9 -> 5,9\n").unwrap();
+
+    // Nothing listens on the discard port
+    let inputs = InputManager::new(&dir)
+        .with_base_url("http://127.0.0.1:9")
+        .with_session("abc");
+
+    assert_eq!(inputs.read(5, false).unwrap(), "0,9 -> 5,9\n");
+    assert!(matches!(inputs.fetch(5), Err(InputError::Http(_, _))));
+
+    let _ = fs::remove_dir_all(&dir);
+}
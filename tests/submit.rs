@@ -0,0 +1,135 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use aoc2021::http::{HttpClient, HttpError, Response, TcpClient};
+use aoc2021::solution::Answer;
+use aoc2021::submit::{Outcome, SubmitError, Submitter};
+
+// Requests made to the fake as (url, cookie, form)
+type Posts = Arc<Mutex<Vec<(String, String, String)>>>;
+
+// Answers every post with the same page
+struct FakeClient {
+    status: u16,
+    page: &'static str,
+    posts: Posts,
+}
+
+impl FakeClient {
+    fn new(status: u16, page: &'static str) -> (Self, Posts) {
+        let posts = Arc::new(Mutex::new(vec![]));
+        let client = FakeClient { status, page, posts: Arc::clone(&posts) };
+
+        (client, posts)
+    }
+}
+
+impl HttpClient for FakeClient {
+    fn get(&self, _url: &str, _cookie: &str) -> Result<Response, HttpError> {
+        Err(HttpError("answers are only posted".to_string()))
+    }
+
+    fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<Response, HttpError> {
+        self.posts.lock().unwrap().push((url.to_string(), cookie.to_string(), form.to_string()));
+
+        Ok(Response {
+            status: self.status,
+            body: self.page.to_string(),
+        })
+    }
+}
+
+const RIGHT: &str = "<main><article><p>That's the right answer!  You are <em>one gold star</em> \
+                     closer to saving your vacation.</p></article></main>";
+
+const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  \
+                        Please wait one minute before trying again.</p></article></main>";
+
+#[test]
+fn posts_the_level_and_answer() {
+    let (client, posts) = FakeClient::new(200, RIGHT);
+
+    let submitter = Submitter::new()
+        .with_base_url("http://127.0.0.1:9/")
+        .with_session("abc")
+        .with_client(Box::new(client));
+
+    let reply = submitter.submit(1, 2, &Answer::from(1491)).unwrap();
+
+    assert_eq!(reply.outcome, Outcome::Correct);
+    assert!(reply.message.starts_with("That's the right answer! You are one gold star closer"));
+
+    assert_eq!(
+        *posts.lock().unwrap(),
+        [(
+            "http://127.0.0.1:9/2021/day/1/answer".to_string(),
+            "session=abc".to_string(),
+            "level=2&answer=1491".to_string()
+        )]
+    );
+}
+
+#[test]
+fn wrong_answers_keep_the_hint() {
+    let (client, _) = FakeClient::new(200, TOO_HIGH);
+    let submitter = Submitter::new().with_session("abc").with_client(Box::new(client));
+
+    let reply = submitter.submit(7, 1, &Answer::from(344736)).unwrap();
+
+    assert_eq!(reply.outcome, Outcome::Incorrect);
+    assert!(reply.message.contains("your answer is too high"));
+}
+
+#[test]
+fn nothing_posted_that_cant_be_accepted() {
+    let (client, posts) = FakeClient::new(200, RIGHT);
+    let submitter = Submitter::new().with_client(Box::new(client));
+
+    assert!(matches!(submitter.submit(1, 1, &Answer::from(1466)), Err(SubmitError::NoSession)));
+
+    let submitter = submitter.with_session("abc");
+    let grid = Answer::Grid(vec!["#.#".to_string()]);
+
+    assert!(matches!(submitter.submit(13, 2, &grid), Err(SubmitError::GridAnswer)));
+    assert!(posts.lock().unwrap().is_empty());
+
+    let (client, _) = FakeClient::new(302, "");
+    let submitter = Submitter::new().with_session("abc").with_client(Box::new(client));
+
+    match submitter.submit(1, 1, &Answer::from(1466)) {
+        Err(e @ SubmitError::Status(_, 302)) => assert!(e.to_string().ends_with("/2021/day/1/answer: status 302")),
+        other => panic!("expecting a 302, got {:?}", other),
+    }
+}
+
+#[test]
+fn tcp_client_sends_the_form() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/2021/day/3/answer", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = vec![];
+        let mut buf = [0; 1024];
+
+        // The form is short, the request ends with it
+        while !request.ends_with(b"answer=198") {
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "request ended early");
+            request.extend_from_slice(&buf[..n]);
+        }
+
+        let _ = write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok");
+        String::from_utf8(request).unwrap()
+    });
+
+    let response = TcpClient::new().post_form(&url, "session=abc", "level=1&answer=198").unwrap();
+    let request = server.join().unwrap();
+
+    assert_eq!(response.body, "ok");
+    assert!(request.starts_with("POST /2021/day/3/answer HTTP/1.1\r\n"));
+    assert!(request.contains("\r\nCookie: session=abc\r\n"));
+    assert!(request.contains("\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: 18\r\n"));
+}